@@ -13,11 +13,44 @@ pub struct NodeLayoutInfo {
     pub inner_sizes: (f32, f32),
 }
 
+/// Identifies a measurement of a node by the sizes of the areas it was measured in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MeasurementKey {
+    element_id: ElementId,
+    sizes: [u32; 6],
+}
+
+impl MeasurementKey {
+    pub fn new(
+        element_id: ElementId,
+        remaining_area: &NodeArea,
+        parent_area: &NodeArea,
+        containing_area: &NodeArea,
+    ) -> Self {
+        Self {
+            element_id,
+            sizes: [
+                remaining_area.width.to_bits(),
+                remaining_area.height.to_bits(),
+                parent_area.width.to_bits(),
+                parent_area.height.to_bits(),
+                containing_area.width.to_bits(),
+                containing_area.height.to_bits(),
+            ],
+        }
+    }
+}
+
 /// Stores all the nodes layout and what nodes should be calculated again on the next check.
 #[derive(Debug, Default)]
 pub struct LayoutMemorizer {
     pub nodes: HashMap<ElementId, NodeLayoutInfo>,
     pub dirty_nodes: HashMap<ElementId, ()>,
+    /// Whether the nodes have children sized with `flex()` along their main axis
+    pub flex_containers: HashMap<ElementId, bool>,
+    /// Areas of the nodes measured without memorizing them, relative to the area they were measured in.
+    /// Only valid during a single layout pass.
+    pub measurements: HashMap<MeasurementKey, NodeArea>,
     #[cfg(debug_assertions)]
     pub dirty_nodes_counter: i32,
 }
@@ -27,6 +60,8 @@ impl LayoutMemorizer {
         Self {
             nodes: HashMap::new(),
             dirty_nodes: HashMap::new(),
+            flex_containers: HashMap::new(),
+            measurements: HashMap::new(),
             #[cfg(debug_assertions)]
             dirty_nodes_counter: 0,
         }
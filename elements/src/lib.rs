@@ -79,6 +79,7 @@ macro_rules! builder_constructors {
 builder_constructors! {
     rect {
         padding: String,
+        margin: String,
         height: String,
         width: String,
         min_height: String,
@@ -90,13 +91,42 @@ builder_constructors! {
         scroll_y: String,
         scroll_x: String,
         direction: String,
+        wrap: String,
         shadow: String,
         radius: String,
+        border: String,
+        border_align: String,
+        opacity: String,
+        transform: String,
+        transform_origin: String,
+        blur: String,
+        backdrop_blur: String,
+        brightness: String,
+        saturate: String,
+        grayscale: String,
         color: String,
         display: String,
+        main_align: String,
+        cross_align: String,
+        gap: String,
+        row_gap: String,
+        column_gap: String,
+        position: String,
+        top: String,
+        right: String,
+        bottom: String,
+        left: String,
+        grid_columns: String,
+        grid_rows: String,
+        grid_column: String,
+        grid_row: String,
+        grid_column_span: String,
+        grid_row_span: String,
+        aspect_ratio: String,
     };
     container {
         padding: String,
+        margin: String,
         height: String,
         width: String,
         min_height: String,
@@ -108,10 +138,38 @@ builder_constructors! {
         scroll_y: String,
         scroll_x: String,
         direction: String,
+        wrap: String,
         shadow: String,
         radius: String,
+        border: String,
+        border_align: String,
+        opacity: String,
+        transform: String,
+        transform_origin: String,
+        blur: String,
+        backdrop_blur: String,
+        brightness: String,
+        saturate: String,
+        grayscale: String,
         color: String,
         display: String,
+        main_align: String,
+        cross_align: String,
+        gap: String,
+        row_gap: String,
+        column_gap: String,
+        position: String,
+        top: String,
+        right: String,
+        bottom: String,
+        left: String,
+        grid_columns: String,
+        grid_rows: String,
+        grid_column: String,
+        grid_row: String,
+        grid_column_span: String,
+        grid_row_span: String,
+        aspect_ratio: String,
     };
     label {
         color: String,
@@ -123,6 +181,10 @@ builder_constructors! {
         align: String,
         max_lines: String,
         font_style: String,
+        decoration: String,
+        letter_spacing: String,
+        word_spacing: String,
+        text_shadow: String,
     };
     paragraph {
         layer: String,
@@ -136,6 +198,10 @@ builder_constructors! {
         line_height: String,
         cursor_id: String,
         direction: String,
+        decoration: String,
+        letter_spacing: String,
+        word_spacing: String,
+        text_shadow: String,
     };
     text {
         color: String,
@@ -146,6 +212,10 @@ builder_constructors! {
         font_family: String,
         line_height: String,
         font_style: String,
+        decoration: String,
+        letter_spacing: String,
+        word_spacing: String,
+        text_shadow: String,
     };
 }
 
@@ -163,6 +233,10 @@ impl image {
         cx.custom_attr("image_data", AttributeValue::Bytes(val), None, false, false)
     }
 
+    pub fn image_path<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("image_path", val, None, false)
+    }
+
     pub fn width<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
         cx.attr("width", val, None, false)
     }
@@ -170,6 +244,26 @@ impl image {
     pub fn height<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
         cx.attr("height", val, None, false)
     }
+
+    pub fn aspect_ratio<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("aspect_ratio", val, None, false)
+    }
+
+    pub fn object_fit<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("object_fit", val, None, false)
+    }
+
+    pub fn object_position<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("object_position", val, None, false)
+    }
+
+    pub fn sampling<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("sampling", val, None, false)
+    }
+
+    pub fn slice<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("slice", val, None, false)
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -189,6 +283,10 @@ impl svg {
         cx.attr("svg_content", val, None, false)
     }
 
+    pub fn color<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("color", val, None, false)
+    }
+
     pub fn width<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
         cx.attr("width", val, None, false)
     }
@@ -196,6 +294,10 @@ impl svg {
     pub fn height<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
         cx.attr("height", val, None, false)
     }
+
+    pub fn aspect_ratio<'a>(&self, cx: NodeFactory<'a>, val: Arguments) -> Attribute<'a> {
+        cx.attr("aspect_ratio", val, None, false)
+    }
 }
 
 pub mod on {
@@ -0,0 +1,56 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    render!(
+        rect {
+            background: "rgb(150, 150, 150)",
+            height: "50%",
+            width: "100%",
+            direction: "horizontal",
+            main_align: "space-between",
+            cross_align: "center",
+            rect {
+                background: "rgb(255, 0, 255)",
+                height: "50",
+                width: "50",
+            }
+            rect {
+                background: "rgb(0, 255, 0)",
+                height: "75",
+                width: "50",
+            }
+            rect {
+                background: "rgb(255, 255, 0)",
+                height: "100",
+                width: "50",
+            }
+        }
+        rect {
+            background: "rgb(200, 200, 200)",
+            height: "50%",
+            width: "100%",
+            direction: "vertical",
+            main_align: "space-evenly",
+            cross_align: "end",
+            rect {
+                background: "rgb(0, 0, 255)",
+                height: "25",
+                width: "100",
+            }
+            rect {
+                background: "rgb(255, 0, 0)",
+                height: "25",
+                width: "150",
+            }
+        }
+    )
+}
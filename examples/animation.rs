@@ -36,7 +36,7 @@ fn app(cx: Scope) -> Element {
                     padding: "20",
                     height: "50",
                     width: "100%",
-                    background: "green",
+                    background: "lime",
                     onclick: start_animation,
                     label {
                         "Start"
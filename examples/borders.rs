@@ -0,0 +1,46 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    render!(
+        rect {
+            background: "white",
+            height: "100%",
+            width: "100%",
+            padding: "60",
+            gap: "30",
+            direction: "horizontal",
+            rect {
+                background: "rgb(235, 235, 235)",
+                border: "4 solid rgb(50, 50, 50)",
+                border_align: "inner",
+                radius: "12 12 0 0",
+                height: "120",
+                width: "120",
+            }
+            rect {
+                border: "3 dashed rgb(0, 120, 255)",
+                border_align: "center",
+                radius: "20",
+                height: "120",
+                width: "120",
+            }
+            rect {
+                background: "rgb(255, 220, 220)",
+                border: "4 dotted crimson",
+                border_align: "outer",
+                radius: "60",
+                height: "120",
+                width: "120",
+            }
+        }
+    )
+}
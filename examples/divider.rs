@@ -37,7 +37,7 @@ fn app(cx: Scope) -> Element {
                     }
                 }
                 rect {
-                    background: "green",
+                    background: "lime",
                     height: "{sizes.3}%",
                     width: "stretch",
                     padding: "30",
@@ -46,7 +46,7 @@ fn Drawer<'a>(cx: Scope<'a, DrawerOptions<'a>>) -> Element<'a> {
             rect {
                 height: "100%",
                 width: "200",
-                background: "gray",
+                background: "#888",
                 layer: "-10",
                 shadow: "5 0 200 25.0 black",
                 &cx.props.body
@@ -135,10 +135,10 @@ fn Button<'a>(cx: Scope<'a, ButtonProps<'a>>) -> Element {
                 height: "100%",
                 padding: "2",
                 radius: "7",
-                background: "green",
+                background: "lime",
                 rect {
                     onmouseover: move |_| {
-                        background.set("gray");
+                        background.set("#888");
                     },
                     onmouseleave: move |_| {
                         background.set("black");
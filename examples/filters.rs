@@ -0,0 +1,60 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let disabled = use_state(&cx, || false);
+    let grayscale = if *disabled.get() { "1" } else { "0" };
+
+    render!(
+        rect {
+            background: "linear-gradient(to right, rgb(255, 90, 90), rgb(90, 120, 255))",
+            height: "100%",
+            width: "100%",
+            padding: "120",
+            gap: "40",
+            direction: "horizontal",
+            rect {
+                background: "rgba(255, 255, 255, 0.3)",
+                backdrop_blur: "12",
+                radius: "16",
+                height: "140",
+                width: "180",
+                padding: "30",
+                label {
+                    color: "white",
+                    "Frosted glass"
+                }
+            }
+            rect {
+                background: "rgb(30, 200, 120)",
+                radius: "16",
+                height: "140",
+                width: "180",
+                padding: "30",
+                grayscale: "{grayscale}",
+                onclick: move |_| disabled.set(!disabled.get()),
+                label {
+                    color: "white",
+                    "Click to toggle"
+                }
+            }
+            rect {
+                background: "rgb(255, 200, 0)",
+                radius: "16",
+                height: "140",
+                width: "180",
+                blur: "4",
+                brightness: "1.2",
+                saturate: "150%",
+            }
+        }
+    )
+}
@@ -0,0 +1,41 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    render!(
+        rect {
+            background: "linear-gradient(to bottom, #1e1e2e, rgb(60, 60, 90))",
+            height: "100%",
+            width: "100%",
+            padding: "40",
+            gap: "20",
+            direction: "horizontal",
+            rect {
+                background: "linear-gradient(45deg, hotpink, orange 60%, gold)",
+                height: "150",
+                width: "150",
+                radius: "15",
+            }
+            rect {
+                background: "radial-gradient(circle, white, rgba(0, 120, 255, 0.5) 60%, transparent)",
+                height: "150",
+                width: "150",
+                radius: "75",
+            }
+            rect {
+                background: "conic-gradient(from 90deg, red, yellow, lime, aqua, blue, magenta, red)",
+                height: "150",
+                width: "150",
+                radius: "75",
+            }
+        }
+    )
+}
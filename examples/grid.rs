@@ -0,0 +1,55 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    render!(
+        rect {
+            background: "rgb(200, 200, 200)",
+            height: "100%",
+            width: "100%",
+            padding: "20",
+            display: "grid",
+            grid_columns: "150, flex(1), flex(2)",
+            grid_rows: "60, auto, calc(20% - 10)",
+            gap: "10",
+            rect {
+                background: "rgb(255, 0, 255)",
+                height: "100%",
+                width: "100%",
+                grid_column_span: "3",
+            }
+            rect {
+                background: "rgb(0, 255, 0)",
+                height: "100%",
+                width: "100%",
+                grid_row_span: "2",
+            }
+            rect {
+                background: "rgb(255, 255, 0)",
+                height: "100%",
+                width: "100%",
+            }
+            rect {
+                background: "rgb(0, 0, 255)",
+                height: "100%",
+                width: "100%",
+            }
+            rect {
+                background: "rgb(255, 0, 0)",
+                height: "100%",
+                width: "100%",
+                grid_column: "1",
+                grid_row: "2",
+                grid_column_span: "2",
+            }
+        }
+    )
+}
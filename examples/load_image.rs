@@ -0,0 +1,52 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let logo = use_image(&cx, "./examples/rust_logo.png");
+
+    let logo = match logo.get() {
+        ImageState::Loading => rsx!(
+            label {
+                color: "black",
+                "Loading..."
+            }
+        ),
+        ImageState::Loaded(bytes) => rsx!(image {
+            image_data: bytes,
+            width: "150",
+            height: "150",
+        }),
+        ImageState::Error(err) => rsx!(
+            label {
+                color: "red",
+                "Couldn't load the image: {err}"
+            }
+        ),
+    };
+
+    render!(
+        rect {
+            width: "100%",
+            height: "100%",
+            padding: "100",
+            gap: "50",
+            direction: "horizontal",
+            logo,
+            // Loaded and decoded by the renderer, a placeholder is shown meanwhile
+            image {
+                image_path: "./examples/rust_logo.png",
+                object_fit: "contain",
+                width: "300",
+                height: "150",
+            }
+        }
+    )
+}
@@ -0,0 +1,34 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+static RUST_LOGO: &[u8] = include_bytes!("./rust_logo.png");
+
+fn app(cx: Scope) -> Element {
+    render!(
+        rect {
+            width: "100%",
+            height: "100%",
+            padding: "100",
+            gap: "50",
+            image {
+                image_data: RUST_LOGO,
+                width: "400",
+                height: "120",
+            }
+            image {
+                image_data: RUST_LOGO,
+                slice: "40",
+                width: "400",
+                height: "120",
+            }
+        }
+    )
+}
@@ -0,0 +1,43 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    render!(
+        rect {
+            background: "rgb(200, 200, 200)",
+            height: "100%",
+            width: "100%",
+            direction: "vertical",
+            position: "relative",
+            rect {
+                background: "rgb(255, 0, 255)",
+                height: "50",
+                width: "50",
+                position: "relative",
+                top: "10",
+                left: "10",
+            }
+            rect {
+                background: "rgb(0, 255, 0)",
+                height: "75",
+                width: "75",
+                position: "absolute",
+                right: "20",
+                bottom: "20",
+            }
+            rect {
+                background: "rgb(255, 255, 0)",
+                height: "50",
+                width: "50",
+            }
+        }
+    )
+}
@@ -32,6 +32,13 @@ fn app(cx: Scope) -> Element {
                 label {
                     "Scroll!"
                 }
+                rect {
+                    shadow: "0 4 150 12 4 rgb(0, 0, 255), inset 0 0 200 10 2 white",
+                    height: "100",
+                    width: "100",
+                    background: "rgb(40, 40, 40)",
+                    radius: "15",
+                }
             }
         }
     )
@@ -12,7 +12,7 @@ fn main() {
 }
 
 fn app(cx: Scope) -> Element {
-    let colors = use_state(&cx, || vec!["green", "blue", "red"]);
+    let colors = use_state(&cx, || vec!["lime", "blue", "red"]);
     let padding = use_state(&cx, || 10);
 
     use_effect(&cx, colors, |colors| async move {
@@ -0,0 +1,50 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    render!(
+        rect {
+            background: "white",
+            height: "100%",
+            width: "100%",
+            padding: "60",
+            gap: "20",
+            color: "black",
+            font_size: "24",
+            label {
+                decoration: "underline",
+                "Underlined"
+            }
+            label {
+                decoration: "line-through wavy red",
+                "Crossed out with a wavy red line"
+            }
+            label {
+                decoration: "underline overline dashed rgb(0, 120, 255)",
+                letter_spacing: "4",
+                "Spaced letters"
+            }
+            paragraph {
+                width: "100%",
+                word_spacing: "12",
+                text {
+                    text_shadow: "2 2 3 rgb(0, 0, 0, 120)",
+                    "Words with room to breathe "
+                }
+                text {
+                    color: "white",
+                    text_shadow: "0 0 4 black, 0 0 8 rgb(0, 120, 255)",
+                    "and a glow"
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,50 @@
+#![cfg_attr(
+    all(not(debug_assertions), target_os = "windows"),
+    windows_subsystem = "windows"
+)]
+
+use freya::prelude::*;
+
+fn main() {
+    launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let angle = use_state(&cx, || 0.0);
+
+    render!(
+        rect {
+            background: "white",
+            height: "100%",
+            width: "100%",
+            padding: "120",
+            gap: "60",
+            direction: "horizontal",
+            rect {
+                background: "rgb(0, 120, 255)",
+                radius: "12",
+                height: "120",
+                width: "120",
+                transform: "rotate({angle}deg)",
+                onclick: move |_| angle.with_mut(|angle| *angle += 15.0),
+                label {
+                    color: "white",
+                    "Click to rotate"
+                }
+            }
+            rect {
+                background: "crimson",
+                height: "120",
+                width: "120",
+                transform: "scale(0.5, 1.5)",
+                transform_origin: "top left",
+            }
+            rect {
+                background: "rgb(30, 200, 120)",
+                height: "120",
+                width: "120",
+                transform: "translate(20, 40) rotate(45deg)",
+            }
+        }
+    )
+}
@@ -6,7 +6,9 @@ use dioxus_router::*;
 use freya_components::*;
 use freya_elements as dioxus_elements;
 use freya_hooks::use_theme;
-use freya_node_state::{AttributeType, NodeState, ShadowSettings};
+use freya_node_state::{
+    AttributeError, AttributeType, Border, Fill, NodeState, SafeDiagnostics, ShadowSettings,
+};
 use skia_safe::Color;
 use std::{
     sync::{Arc, Mutex},
@@ -27,6 +29,7 @@ struct TreeNode {
 #[derive(Props)]
 pub struct DevToolsProps {
     rdom: Arc<Mutex<RealDom<NodeState>>>,
+    diagnostics: SafeDiagnostics,
 }
 
 // Hacky stuff over here
@@ -40,13 +43,23 @@ impl PartialEq for DevToolsProps {
 pub fn DevTools(cx: Scope<DevToolsProps>) -> Element {
     let children = use_state(&cx, Vec::<TreeNode>::new);
     let setter = children.setter();
+    let errors = use_state(&cx, Vec::<AttributeError>::new);
+    let errors_setter = errors.setter();
 
     use_effect(&cx, (), move |_| {
         let rdom = cx.props.rdom.clone();
+        let diagnostics = cx.props.diagnostics.clone();
         async move {
+            let mut last_errors = Vec::new();
             loop {
                 sleep(Duration::from_millis(25)).await;
 
+                let new_errors = diagnostics.lock().unwrap().errors().to_vec();
+                if new_errors != last_errors {
+                    last_errors = new_errors.clone();
+                    errors_setter(new_errors);
+                }
+
                 let rdom = rdom.lock().unwrap();
                 let mut children = Vec::new();
 
@@ -106,6 +119,10 @@ pub fn DevTools(cx: Scope<DevToolsProps>) -> Element {
                     to: "/elements",
                     label: "Elements"
                 }
+                TabButton {
+                    to: "/diagnostics",
+                    label: "Diagnostics"
+                }
                 TabButton {
                     to: "/settings",
                     label: "Settings"
@@ -158,6 +175,12 @@ pub fn DevTools(cx: Scope<DevToolsProps>) -> Element {
                     )
                 })
             }
+            Route {
+                to: "/diagnostics",
+                Diagnostics {
+                    errors: errors
+                }
+            }
             Route {
                 to: "/settings",
                 label {
@@ -202,6 +225,27 @@ fn NodesTree<'a>(
     })
 }
 
+#[allow(non_snake_case)]
+#[inline_props]
+fn Diagnostics<'a>(cx: Scope<'a>, errors: &'a Vec<AttributeError>) -> Element<'a> {
+    let errors = errors.iter().enumerate().map(|(i, error)| {
+        rsx! {
+            Property {
+                key: "{i}",
+                name: "<{error.element}> {error.attribute}",
+                value: format!("\"{}\" ({})", error.value, error.reason)
+            }
+        }
+    });
+
+    render!(ScrollView {
+        width: "100%",
+        height: "calc(100% - 35)",
+        show_scrollbar: true,
+        errors
+    })
+}
+
 #[derive(Props)]
 struct TabsBarProps<'a> {
     pub children: Element<'a>,
@@ -330,6 +374,20 @@ fn NodeInspectorStyle<'a>(cx: Scope<'a>, node: &'a TreeNode) -> Element<'a> {
                                 }
                             }
                         }
+                        AttributeType::Tracks(tracks) => {
+                            let tracks = tracks
+                                .iter()
+                                .map(|track| track.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ");
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: tracks
+                                }
+                            }
+                        }
                         AttributeType::Color(color) => {
                             rsx!{
                                 ColorfulProperty {
@@ -339,6 +397,24 @@ fn NodeInspectorStyle<'a>(cx: Scope<'a>, node: &'a TreeNode) -> Element<'a> {
                                 }
                             }
                         }
+                        AttributeType::Fill(Fill::Color(color)) => {
+                            rsx!{
+                                ColorfulProperty {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    color: color
+                                }
+                            }
+                        }
+                        AttributeType::Fill(fill) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: fill.to_string()
+                                }
+                            }
+                        }
                         AttributeType::Text(text) => {
                             rsx!{
                                 Property {
@@ -357,6 +433,33 @@ fn NodeInspectorStyle<'a>(cx: Scope<'a>, node: &'a TreeNode) -> Element<'a> {
                                 }
                             }
                         }
+                        AttributeType::Position(position) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: position.to_string()
+                                }
+                            }
+                        }
+                        AttributeType::Wrap(wrap) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: wrap.to_string()
+                                }
+                            }
+                        }
+                        AttributeType::Alignment(alignment) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: alignment.to_string()
+                                }
+                            }
+                        }
                         AttributeType::Display(display) => {
                             rsx!{
                                 Property {
@@ -366,12 +469,102 @@ fn NodeInspectorStyle<'a>(cx: Scope<'a>, node: &'a TreeNode) -> Element<'a> {
                                 }
                             }
                         }
-                        AttributeType::Shadow(shadow_settings) => {
+                        AttributeType::Transform(transform) => {
+                            let transform = transform
+                                .iter()
+                                .map(|transform| transform.to_string())
+                                .collect::<Vec<String>>()
+                                .join(" ");
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: transform
+                                }
+                            }
+                        }
+                        AttributeType::TransformOrigin(transform_origin) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: transform_origin.to_string()
+                                }
+                            }
+                        }
+                        AttributeType::ObjectFit(object_fit) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: object_fit.to_string()
+                                }
+                            }
+                        }
+                        AttributeType::ObjectPosition(object_position) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: object_position.to_string()
+                                }
+                            }
+                        }
+                        AttributeType::ImageSampling(sampling) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: sampling.to_string()
+                                }
+                            }
+                        }
+                        AttributeType::Decoration(decoration) => {
+                            rsx!{
+                                Property {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    value: decoration.to_string()
+                                }
+                            }
+                        }
+                        AttributeType::TextShadows(text_shadows) => {
+                            let text_shadows = text_shadows
+                                .iter()
+                                .map(|text_shadow| text_shadow.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ");
                             rsx!{
-                                ShadowProperty {
+                                Property {
                                     key: "{i}",
                                     name: "{name}",
-                                    shadow_settings: shadow_settings
+                                    value: text_shadows
+                                }
+                            }
+                        }
+                        AttributeType::Border(border) => {
+                            rsx!{
+                                BorderProperty {
+                                    key: "{i}",
+                                    name: "{name}",
+                                    border: border
+                                }
+                            }
+                        }
+                        AttributeType::Shadows(shadows) => {
+                            rsx!{
+                                rect {
+                                    key: "{i}",
+                                    width: "100%",
+                                    shadows.iter().enumerate().map(|(j, shadow_settings)| {
+                                        rsx!{
+                                            ShadowProperty {
+                                                key: "{j}",
+                                                name: "{name}",
+                                                shadow_settings: shadow_settings
+                                            }
+                                        }
+                                    })
                                 }
                             }
                         }
@@ -469,6 +662,7 @@ fn ShadowProperty<'a>(
     shadow_settings: &'a ShadowSettings,
 ) -> Element<'a> {
     let color = shadow_settings.color.to_rgb();
+    let inset = if shadow_settings.inset { "inset " } else { "" };
     render!(
         container {
             height: "30",
@@ -489,7 +683,62 @@ fn ShadowProperty<'a>(
                 text {
                     font_size: "15",
                     color: "rgb(252,181,172)",
-                    "{shadow_settings.x} {shadow_settings.y} {shadow_settings.intensity} {shadow_settings.size}"
+                    "{inset}{shadow_settings.x} {shadow_settings.y} {shadow_settings.intensity} {shadow_settings.blur} {shadow_settings.spread}"
+                }
+            }
+            rect {
+                width: "5"
+            }
+            rect {
+                width: "17",
+                height: "17",
+                radius: "5",
+                background: "white",
+                padding: "10",
+                rect {
+                    radius: "3",
+                    width: "100%",
+                    height: "100%",
+                    background: "rgb({color.r}, {color.g}, {color.b})",
+                }
+            }
+            rect {
+                width: "5"
+            }
+            label {
+                font_size: "15",
+                color: "rgb(252,181,172)",
+                "rgb({color.r}, {color.g}, {color.b})"
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[inline_props]
+fn BorderProperty<'a>(cx: Scope<'a>, name: &'a str, border: &'a Border) -> Element<'a> {
+    let color = border.color.to_rgb();
+    render!(
+        container {
+            height: "30",
+            width: "100%",
+            direction: "horizontal",
+            padding: "40",
+            paragraph {
+                text {
+                    font_size: "15",
+                    color: "rgb(71, 180, 240)",
+                    "{name}"
+                }
+                text {
+                    font_size: "15",
+                    color: "rgb(215, 215, 215)",
+                    ": "
+                }
+                text {
+                    font_size: "15",
+                    color: "rgb(252,181,172)",
+                    "{border.width} {border.style} {border.alignment}"
                 }
             }
             rect {
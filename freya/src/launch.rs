@@ -3,9 +3,9 @@ use dioxus::prelude::{Component, UnboundedSender, VirtualDom};
 use dioxus_core::SchedulerMsg;
 use dioxus_native_core::real_dom::RealDom;
 use freya_common::LayoutMemorizer;
-use freya_node_state::NodeState;
+use freya_node_state::{NodeState, SafeDiagnostics};
 use freya_renderer::run;
-use freya_renderer::WindowConfig;
+use freya_renderer::{RenderBackend, WindowConfig};
 use std::sync::Arc;
 use std::sync::Mutex;
 use tracing::info;
@@ -46,6 +46,9 @@ pub fn launch(app: Component<()>) {
             transparent: false,
             title: "Freya",
             state: None,
+            backend: RenderBackend::Gpu,
+            on_frame: None,
+            controller: None,
         },
     )])
 }
@@ -85,6 +88,9 @@ pub fn launch_with_title(app: Component<()>, title: &'static str) {
             transparent: false,
             title,
             state: None,
+            backend: RenderBackend::Gpu,
+            on_frame: None,
+            controller: None,
         },
     )])
 }
@@ -122,6 +128,9 @@ pub fn launch_with_props(app: Component<()>, title: &'static str, (width, height
             transparent: false,
             title,
             state: None,
+            backend: RenderBackend::Gpu,
+            on_frame: None,
+            controller: None,
         },
     )])
 }
@@ -133,6 +142,7 @@ pub fn launch_with_props(app: Component<()>, title: &'static str, (width, height
 /// - Decorations
 /// - Transparency
 /// - Window title
+/// - Render backend, use [`RenderBackend::Raster`] to render offscreen without a GPU
 ///
 /// # Example
 /// ```rust
@@ -170,17 +180,19 @@ pub fn launch_cfg<T: 'static + Clone + Send>(wins_config: Vec<(Component<()>, Wi
                 Arc::new(Mutex::new(None));
 
             let layout_memorizer = Arc::new(Mutex::new(LayoutMemorizer::new()));
+            let diagnostics = SafeDiagnostics::default();
             let state = win.state.clone();
 
             {
                 let layout_memorizer = layout_memorizer.clone();
+                let diagnostics = diagnostics.clone();
                 let rdom = rdom.clone();
                 let event_emitter = event_emitter.clone();
                 std::thread::spawn(move || {
                     let mut dom = {
                         #[cfg(feature = "devtools")]
                         {
-                            with_devtools(rdom.clone(), root)
+                            with_devtools(rdom.clone(), diagnostics.clone(), root)
                         }
 
                         #[cfg(not(feature = "devtools"))]
@@ -197,7 +209,8 @@ pub fn launch_cfg<T: 'static + Clone + Send>(wins_config: Vec<(Component<()>, Wi
                     let to_update = rdom.lock().unwrap().apply_mutations(vec![muts]);
                     let mut ctx = AnyMap::new();
 
-                    ctx.insert(layout_memorizer.clone());
+                    ctx.insert(diagnostics.clone());
+                    ctx.insert((layout_memorizer.clone(), diagnostics.clone()));
 
                     rdom.lock().unwrap().update_state(&dom, to_update, ctx);
 
@@ -222,17 +235,21 @@ pub fn launch_cfg<T: 'static + Clone + Send>(wins_config: Vec<(Component<()>, Wi
                                 }
 
                                 let mut ctx = AnyMap::new();
-                                ctx.insert(layout_memorizer.clone());
+                                ctx.insert(diagnostics.clone());
+                                ctx.insert((layout_memorizer.clone(), diagnostics.clone()));
 
                                 if !to_update.is_empty() {
                                     info!("Updated Dioxus DOM with {} mutations.", to_update.len());
-                                    rdom.lock().unwrap().update_state(&dom, to_update, ctx);
+                                    let mut rdom = rdom.lock().unwrap();
+                                    rdom.update_state(&dom, to_update, ctx);
+                                    // Removed nodes don't have errors anymore
+                                    diagnostics.lock().unwrap().remove_missing_nodes(&rdom);
                                 }
                             }
                         });
                 });
             }
-            (rdom, event_emitter, layout_memorizer, win)
+            (rdom, event_emitter, layout_memorizer, diagnostics, win)
         })
         .collect();
 
@@ -247,6 +264,7 @@ use dioxus::prelude::{
 #[cfg(feature = "devtools")]
 fn with_devtools(
     rdom: Arc<Mutex<RealDom<NodeState>>>,
+    diagnostics: SafeDiagnostics,
     root: fn(cx: Scope) -> Element,
 ) -> VirtualDom {
     use crate::devtools::DevTools;
@@ -273,7 +291,8 @@ fn with_devtools(
                     width: "350",
                     ThemeProvider {
                         DevTools {
-                            rdom: cx.props.rdom.clone()
+                            rdom: cx.props.rdom.clone(),
+                            diagnostics: cx.props.diagnostics.clone()
                         }
                     }
                 }
@@ -284,7 +303,15 @@ fn with_devtools(
     struct DomProps {
         root: fn(cx: Scope) -> Element,
         rdom: Arc<Mutex<RealDom<NodeState>>>,
+        diagnostics: SafeDiagnostics,
     }
 
-    VirtualDom::new_with_props(app, DomProps { root, rdom })
+    VirtualDom::new_with_props(
+        app,
+        DomProps {
+            root,
+            rdom,
+            diagnostics,
+        },
+    )
 }
@@ -35,7 +35,7 @@ pub use dioxus;
 pub use freya_components;
 pub use freya_elements as dioxus_elements;
 pub use freya_hooks;
-pub use freya_renderer::WindowConfig;
+pub use freya_renderer::{OffscreenController, RenderBackend, WindowConfig};
 
 pub mod prelude {
     pub use crate::launch::*;
@@ -44,6 +44,6 @@ pub mod prelude {
     pub use freya_elements as dioxus_elements;
     pub use freya_elements::*;
     pub use freya_hooks::*;
-    pub use freya_renderer::WindowConfig;
+    pub use freya_renderer::{OffscreenController, RenderBackend, WindowConfig};
     pub use tracing;
 }
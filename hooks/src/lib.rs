@@ -1,11 +1,13 @@
 mod use_animation;
 mod use_editable;
 mod use_focus;
+mod use_image;
 mod use_node;
 mod use_theme;
 
 pub use use_animation::*;
 pub use use_editable::*;
 pub use use_focus::*;
+pub use use_image::*;
 pub use use_node::*;
 pub use use_theme::*;
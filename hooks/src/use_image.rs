@@ -0,0 +1,53 @@
+use dioxus::prelude::{use_effect, use_ref, use_state, ScopeState, UseState};
+use std::sync::Arc;
+use tokio::task::spawn_blocking;
+
+/// State of an image loaded with [`use_image`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImageState {
+    Loading,
+    /// The encoded bytes of the image, ready to be passed to `image_data`
+    Loaded(Arc<Vec<u8>>),
+    /// Why the file couldn't be read
+    Error(String),
+}
+
+/// Load an image file in a background task.
+/// Elements showing the same bytes share the decoded image, even if they were loaded by different components.
+pub fn use_image<'a>(cx: &'a ScopeState, path: &str) -> &'a UseState<ImageState> {
+    let image = use_state(cx, || ImageState::Loading);
+    let current_path = use_ref(cx, String::new);
+
+    use_effect(cx, &path.to_string(), {
+        let image = image.clone();
+        let current_path = current_path.clone();
+        move |path| async move {
+            *current_path.write_silent() = path.clone();
+
+            if *image.get() != ImageState::Loading {
+                image.set(ImageState::Loading);
+            }
+
+            let loaded = load_file(path.clone()).await;
+
+            // The path might have changed while it was loading
+            if *current_path.read() != path {
+                return;
+            }
+
+            match loaded {
+                Ok(bytes) => image.set(ImageState::Loaded(bytes)),
+                Err(err) => image.set(ImageState::Error(err)),
+            }
+        }
+    });
+
+    image
+}
+
+async fn load_file(path: String) -> Result<Arc<Vec<u8>>, String> {
+    spawn_blocking(move || std::fs::read(path).map(Arc::new))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())
+}
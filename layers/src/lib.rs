@@ -22,6 +22,20 @@ pub struct RenderData {
     pub node_type: NodeType,
 }
 
+impl RenderData {
+    /// Whether the node is composited along with its descendants, e.g to fade or filter them as a whole.
+    /// Only `rect` and `container` elements support `opacity` and filters.
+    pub fn is_group(&self) -> bool {
+        let is_box = matches!(
+            &self.node_type,
+            NodeType::Element { tag, .. } if tag == "rect" || tag == "container"
+        );
+        let style = &self.node_state.style;
+        is_box
+            && (style.opacity.map_or(false, |opacity| opacity < 1.0) || !style.filters.is_empty())
+    }
+}
+
 impl Layers {
     pub fn calculate_layer(
         &mut self,
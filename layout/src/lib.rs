@@ -2,67 +2,79 @@ use std::sync::{Arc, Mutex};
 
 use dioxus::core::ElementId;
 use dioxus_native_core::real_dom::NodeType;
-use freya_common::{LayoutMemorizer, NodeArea, NodeLayoutInfo, NodeReferenceLayout};
+use freya_common::{
+    LayoutMemorizer, MeasurementKey, NodeArea, NodeLayoutInfo, NodeReferenceLayout,
+};
 use freya_layers::{Layers, NodeData};
 use freya_node_state::{
-    CalcType, CursorMode, CursorReference, DirectionMode, DisplayMode, NodeState, SizeMode,
+    AlignmentMode, CalcType, CursorMode, CursorReference, DirectionMode, DisplayMode, NodeState,
+    PositionMode, Size, SizeMode, WrapMode,
 };
-use skia_safe::textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle, TextStyle};
-
-pub fn run_calculations(calcs: &Vec<CalcType>, parent_area_value: f32) -> f32 {
-    let mut prev_number: Option<f32> = None;
-    let mut prev_op: Option<CalcType> = None;
-
-    let mut calc_with_op = |val: f32, prev_op: Option<CalcType>| {
-        if let Some(op) = prev_op {
-            match op {
-                CalcType::Sub => {
-                    prev_number = Some(prev_number.unwrap() - val);
-                }
-                CalcType::Add => {
-                    prev_number = Some(prev_number.unwrap() + val);
-                }
-                CalcType::Mul => {
-                    prev_number = Some(prev_number.unwrap() * val);
-                }
-                CalcType::Div => {
-                    prev_number = Some(prev_number.unwrap() / val);
-                }
-                _ => {}
-            }
-        } else {
-            prev_number = Some(val);
-        }
-    };
-
-    for calc in calcs {
-        match calc {
-            CalcType::Percentage(per) => {
-                let val = (parent_area_value / 100.0 * per).round();
-
-                calc_with_op(val, prev_op);
+use skia_safe::textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle};
+
+/// Sizes that the relative units of a node are resolved against
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct UnitsContext {
+    /// Area of the window, used by `vw` and `vh`
+    pub viewport: NodeArea,
+    /// Font size of the node, used by `em`
+    pub font_size: f32,
+    /// Font size of the root node, used by `rem`
+    pub root_font_size: f32,
+}
 
-                prev_op = None;
-            }
-            CalcType::Manual(val) => {
-                calc_with_op(*val, prev_op);
-                prev_op = None;
-            }
-            _ => prev_op = Some(*calc),
+impl UnitsContext {
+    /// Units of the given node, `em` is relative to it's own font size
+    fn for_node(self, node_data: &NodeData) -> Self {
+        Self {
+            font_size: node_data.node.state.font_style.font_size,
+            ..self
         }
     }
+}
 
-    prev_number.unwrap()
+/// Evaluate a `calc()` expression against the size of the parent area
+pub fn run_calculations(calc: &CalcType, parent_area_value: f32, units: &UnitsContext) -> f32 {
+    let run = |calc: &CalcType| run_calculations(calc, parent_area_value, units);
+
+    match calc {
+        CalcType::Manual(val) => *val,
+        CalcType::Percentage(per) => (parent_area_value / 100.0 * per).round(),
+        CalcType::ViewportWidth(per) => units.viewport.width / 100.0 * per,
+        CalcType::ViewportHeight(per) => units.viewport.height / 100.0 * per,
+        CalcType::Em(val) => units.font_size * val,
+        CalcType::Rem(val) => units.root_font_size * val,
+        CalcType::Neg(val) => -run(val),
+        CalcType::Add(lhs, rhs) => run(lhs) + run(rhs),
+        CalcType::Sub(lhs, rhs) => run(lhs) - run(rhs),
+        CalcType::Mul(lhs, rhs) => run(lhs) * run(rhs),
+        CalcType::Div(lhs, rhs) => run(lhs) / run(rhs),
+        CalcType::Min(values) => values.iter().map(run).fold(f32::INFINITY, f32::min),
+        CalcType::Max(values) => values.iter().map(run).fold(f32::NEG_INFINITY, f32::max),
+        CalcType::Clamp(min, val, max) => run(val).min(run(max)).max(run(min)),
+    }
 }
 
 /// Calculate the are of a node considering it's parent area
-fn calculate_area(node_data: &NodeData, mut area: NodeArea, parent_area: NodeArea) -> NodeArea {
+fn calculate_area(
+    node_data: &NodeData,
+    mut area: NodeArea,
+    parent_area: NodeArea,
+    units: &UnitsContext,
+) -> NodeArea {
+    // The margin is space taken from the available area
+    let margin = node_data.node.state.size.margin;
+    area.x += margin.3;
+    area.y += margin.0;
+    area.width -= margin.1 + margin.3;
+    area.height -= margin.0 + margin.2;
+
     let calculate = |value: &SizeMode, area_value: f32, parent_area_value: f32| -> f32 {
         match value {
             &SizeMode::Manual(v) => v,
             SizeMode::Percentage(per) => (parent_area_value / 100.0 * per).round(),
-            SizeMode::Auto => area_value,
-            SizeMode::Calculation(calcs) => run_calculations(calcs, parent_area_value),
+            SizeMode::Auto | SizeMode::Flex(_) => area_value,
+            SizeMode::Calculation(calcs) => run_calculations(calcs, parent_area_value, units),
         }
     };
 
@@ -83,9 +95,9 @@ fn calculate_area(node_data: &NodeData, mut area: NodeArea, parent_area: NodeAre
                     area_value
                 }
             }
-            SizeMode::Auto => area_value,
+            SizeMode::Auto | SizeMode::Flex(_) => area_value,
             SizeMode::Calculation(calcs) => {
-                let by_calcs = run_calculations(calcs, parent_area_value);
+                let by_calcs = run_calculations(calcs, parent_area_value, units);
                 if by_calcs > area_value {
                     by_calcs
                 } else {
@@ -112,9 +124,9 @@ fn calculate_area(node_data: &NodeData, mut area: NodeArea, parent_area: NodeAre
                     by_per
                 }
             }
-            SizeMode::Auto => area_value,
+            SizeMode::Auto | SizeMode::Flex(_) => area_value,
             SizeMode::Calculation(calcs) => {
-                let by_calcs = run_calculations(calcs, parent_area_value);
+                let by_calcs = run_calculations(calcs, parent_area_value, units);
                 if by_calcs > area_value {
                     area_value
                 } else {
@@ -165,11 +177,371 @@ fn calculate_area(node_data: &NodeData, mut area: NodeArea, parent_area: NodeAre
         parent_area.width,
     );
 
+    // Derive the `auto` axis from the other one to keep the aspect ratio
+    let size = &node_data.node.state.size;
+    if let Some(ratio) = size.aspect_ratio {
+        if SizeMode::Auto == size.width && SizeMode::Auto != size.height {
+            area.width = calculate_max(
+                &size.max_width,
+                calculate_min(&size.min_width, area.height * ratio, parent_area.width),
+                parent_area.width,
+            );
+        } else if SizeMode::Auto == size.height {
+            area.height = calculate_max(
+                &size.max_height,
+                calculate_min(&size.min_height, area.width / ratio, parent_area.height),
+                parent_area.height,
+            );
+        }
+    }
+
     area
 }
 
 type NodeResolver<T> = fn(&ElementId, &mut T) -> Option<NodeData>;
 
+/// Expand an area with the given margin
+fn with_margin(area: NodeArea, margin: (f32, f32, f32, f32)) -> NodeArea {
+    NodeArea {
+        x: area.x - margin.3,
+        y: area.y - margin.0,
+        width: area.width + margin.1 + margin.3,
+        height: area.height + margin.0 + margin.2,
+    }
+}
+
+/// Area and sizing of a child measured in a first pass
+#[derive(Debug, Clone)]
+struct MeasuredChild {
+    area: NodeArea,
+    size: Size,
+    units: UnitsContext,
+}
+
+/// Offsets, spacing and sizes used to position the children of a node along it's main and cross axis
+#[derive(Default, Debug, Clone)]
+struct ChildrenAlignment {
+    /// Offset of the first child along the main axis
+    main_offset: f32,
+    /// Extra space between every two consecutive children along the main axis
+    spacing: f32,
+    /// Offset of every child along the cross axis
+    cross_offsets: Vec<f32>,
+    /// Size along the main axis of every flex child
+    main_sizes: Vec<Option<f32>>,
+    /// Whether the children are being measured to calculate their alignment,
+    /// flex children take no space until the space left by their siblings is known
+    measuring: bool,
+}
+
+/// Resolve a `min_*`, `max_*` or inset size against the parent's inner area
+fn resolve_limit(value: &SizeMode, parent_area_value: f32, units: &UnitsContext) -> Option<f32> {
+    match value {
+        SizeMode::Manual(v) => Some(*v),
+        SizeMode::Percentage(per) => Some((parent_area_value / 100.0 * per).round()),
+        SizeMode::Calculation(calcs) => Some(run_calculations(calcs, parent_area_value, units)),
+        SizeMode::Auto | SizeMode::Flex(_) => None,
+    }
+}
+
+/// Offset of a `relative` node from it's place in the flow
+fn relative_offset(size: &Size, parent_area: NodeArea, units: &UnitsContext) -> (f32, f32) {
+    let x = resolve_limit(&size.left, parent_area.width, units)
+        .or_else(|| resolve_limit(&size.right, parent_area.width, units).map(|right| -right))
+        .unwrap_or(0.0);
+    let y = resolve_limit(&size.top, parent_area.height, units)
+        .or_else(|| resolve_limit(&size.bottom, parent_area.height, units).map(|bottom| -bottom))
+        .unwrap_or(0.0);
+    (x, y)
+}
+
+/// Area available to an `absolute` node inside it's containing area
+fn absolute_area(size: &Size, containing_area: NodeArea, units: &UnitsContext) -> NodeArea {
+    let top = resolve_limit(&size.top, containing_area.height, units).unwrap_or(0.0);
+    let right = resolve_limit(&size.right, containing_area.width, units).unwrap_or(0.0);
+    let bottom = resolve_limit(&size.bottom, containing_area.height, units).unwrap_or(0.0);
+    let left = resolve_limit(&size.left, containing_area.width, units).unwrap_or(0.0);
+    NodeArea {
+        x: containing_area.x + left,
+        y: containing_area.y + top,
+        width: containing_area.width - left - right,
+        height: containing_area.height - top - bottom,
+    }
+}
+
+/// Stick an `absolute` node to the right or bottom edges of it's containing area
+/// when it only has a `right` or `bottom` inset
+fn anchor_absolute_area(
+    size: &Size,
+    node_area: &mut NodeArea,
+    containing_area: NodeArea,
+    units: &UnitsContext,
+) {
+    if resolve_limit(&size.left, containing_area.width, units).is_none() {
+        if let Some(right) = resolve_limit(&size.right, containing_area.width, units) {
+            node_area.x = containing_area.x + containing_area.width - right - node_area.width;
+        }
+    }
+    if resolve_limit(&size.top, containing_area.height, units).is_none() {
+        if let Some(bottom) = resolve_limit(&size.bottom, containing_area.height, units) {
+            node_area.y = containing_area.y + containing_area.height - bottom - node_area.height;
+        }
+    }
+}
+
+/// Get the size, min size and max size of a node along the given direction
+fn main_axis_sizes(direction: DirectionMode, size: &Size) -> (&SizeMode, &SizeMode, &SizeMode) {
+    match direction {
+        DirectionMode::Horizontal => (&size.width, &size.min_width, &size.max_width),
+        _ => (&size.height, &size.min_height, &size.max_height),
+    }
+}
+
+/// Get the sum of both margins of a node along the given direction
+fn main_axis_margin(direction: DirectionMode, size: &Size) -> f32 {
+    match direction {
+        DirectionMode::Horizontal => size.margin.1 + size.margin.3,
+        _ => size.margin.0 + size.margin.2,
+    }
+}
+
+/// Divide the free space among the flex children by their weight, respecting their `min_*`/`max_*` sizes
+fn distribute_flex_space(
+    direction: DirectionMode,
+    free_space: f32,
+    inner_main_size: f32,
+    children: &[MeasuredChild],
+) -> Vec<Option<f32>> {
+    let mut main_sizes: Vec<Option<f32>> = vec![None; children.len()];
+    let mut frozen = vec![false; children.len()];
+    let mut free_space = free_space.max(0.0);
+
+    loop {
+        let total_weight: f32 = children
+            .iter()
+            .zip(&frozen)
+            .filter_map(
+                |(child, frozen)| match main_axis_sizes(direction, &child.size).0 {
+                    SizeMode::Flex(weight) if !frozen => Some(*weight),
+                    _ => None,
+                },
+            )
+            .sum();
+
+        let mut clamped_space = 0.0;
+        let mut any_clamped = false;
+
+        for (i, child) in children.iter().enumerate() {
+            let (main_mode, min_mode, max_mode) = main_axis_sizes(direction, &child.size);
+            if let SizeMode::Flex(weight) = main_mode {
+                if frozen[i] {
+                    continue;
+                }
+                // Flex children with no weight take no space
+                let share = if total_weight > 0.0 {
+                    free_space * weight / total_weight
+                } else {
+                    0.0
+                };
+
+                // Limits don't include the margin
+                let margin = main_axis_margin(direction, &child.size);
+                let mut size = (share - margin).max(0.0);
+                let mut is_clamped = false;
+                if let Some(max) = resolve_limit(max_mode, inner_main_size, &child.units) {
+                    if size > max {
+                        size = max;
+                        is_clamped = true;
+                    }
+                }
+                if let Some(min) = resolve_limit(min_mode, inner_main_size, &child.units) {
+                    if size < min {
+                        size = min;
+                        is_clamped = true;
+                    }
+                }
+                let size = size + margin;
+                main_sizes[i] = Some(size);
+
+                if is_clamped {
+                    frozen[i] = true;
+                    any_clamped = true;
+                    clamped_space += size;
+                }
+            }
+        }
+
+        if !any_clamped || total_weight <= 0.0 {
+            break;
+        }
+
+        // Clamped children keep their size and the rest of the space is distributed again
+        free_space = (free_space - clamped_space).max(0.0);
+    }
+
+    main_sizes
+}
+
+/// Calculate how the children must be sized and aligned given their pre-measured areas
+fn align_children(
+    node_data: &NodeData,
+    inner_area: NodeArea,
+    children: &[MeasuredChild],
+) -> ChildrenAlignment {
+    let size = &node_data.node.state.size;
+
+    if children.is_empty() {
+        return ChildrenAlignment::default();
+    }
+
+    let (main_size, cross_size): (fn(&NodeArea) -> f32, fn(&NodeArea) -> f32) = match size.direction
+    {
+        DirectionMode::Horizontal => (|a| a.width, |a| a.height),
+        _ => (|a| a.height, |a| a.width),
+    };
+
+    let is_flex = |child: &MeasuredChild| {
+        matches!(
+            main_axis_sizes(size.direction, &child.size).0,
+            SizeMode::Flex(_)
+        )
+    };
+
+    let children_count = children.len() as f32;
+    let gaps_size = size.gap_for(size.direction) * (children_count - 1.0);
+
+    // Flex children take the space left by the rest of children and the gaps between them
+    let fixed_main_size: f32 = children
+        .iter()
+        .filter(|child| !is_flex(child))
+        .map(|child| main_size(&child.area))
+        .sum::<f32>()
+        + gaps_size;
+    let main_sizes = distribute_flex_space(
+        size.direction,
+        main_size(&inner_area) - fixed_main_size,
+        main_size(&inner_area),
+        children,
+    );
+
+    let children_main_size: f32 = children
+        .iter()
+        .zip(&main_sizes)
+        .map(|(child, flex_size)| flex_size.unwrap_or_else(|| main_size(&child.area)))
+        .sum::<f32>()
+        + gaps_size;
+    let free_space = main_size(&inner_area) - children_main_size;
+    let positive_free_space = free_space.max(0.0);
+
+    let (main_offset, spacing) = match size.main_align {
+        AlignmentMode::Start => (0.0, 0.0),
+        AlignmentMode::Center => (free_space / 2.0, 0.0),
+        AlignmentMode::End => (free_space, 0.0),
+        AlignmentMode::SpaceBetween => {
+            if children_count > 1.0 {
+                (0.0, positive_free_space / (children_count - 1.0))
+            } else {
+                (0.0, 0.0)
+            }
+        }
+        AlignmentMode::SpaceAround => {
+            let spacing = positive_free_space / children_count;
+            (spacing / 2.0, spacing)
+        }
+        AlignmentMode::SpaceEvenly => {
+            let spacing = positive_free_space / (children_count + 1.0);
+            (spacing, spacing)
+        }
+    };
+
+    let cross_offsets = children
+        .iter()
+        .map(|child| {
+            let free_space = cross_size(&inner_area) - cross_size(&child.area);
+            match size.cross_align {
+                AlignmentMode::Center => free_space / 2.0,
+                AlignmentMode::End => free_space,
+                _ => 0.0,
+            }
+        })
+        .collect();
+
+    ChildrenAlignment {
+        main_offset,
+        spacing,
+        cross_offsets,
+        main_sizes,
+        measuring: false,
+    }
+}
+
+/// Check if a child placed at the remaining area would overflow the line started at `line_start`
+fn overflows_line(
+    direction: DirectionMode,
+    remaining_area: &NodeArea,
+    line_start: &NodeArea,
+    child_area: &NodeArea,
+) -> bool {
+    match direction {
+        DirectionMode::Horizontal => {
+            remaining_area.x + child_area.width > line_start.x + line_start.width
+        }
+        DirectionMode::Vertical => {
+            remaining_area.y + child_area.height > line_start.y + line_start.height
+        }
+        DirectionMode::Both => false,
+    }
+}
+
+/// Check if any of the node's children is sized with `flex()` along the node's main axis,
+/// it's memorized until the node or any of it's children change
+fn has_flex_children<T>(
+    node_data: &NodeData,
+    must_recalculate: bool,
+    resolver_options: &mut T,
+    node_resolver: NodeResolver<T>,
+    layout_memorizer: &Arc<Mutex<LayoutMemorizer>>,
+) -> bool {
+    let children = match &node_data.node.node_type {
+        NodeType::Element { children, .. } => children,
+        _ => return false,
+    };
+
+    if !must_recalculate {
+        let layout_memorizer = layout_memorizer.lock().unwrap();
+        let children_changed = children.iter().any(|child| {
+            layout_memorizer.is_dirty(child) || !layout_memorizer.nodes.contains_key(child)
+        });
+        if !children_changed {
+            if let Some(has_flex_children) =
+                layout_memorizer.flex_containers.get(&node_data.node.id)
+            {
+                return *has_flex_children;
+            }
+        }
+    }
+
+    let direction = node_data.node.state.size.direction;
+    let has_flex_children = children.iter().any(|child| {
+        node_resolver(child, resolver_options)
+            .map(|child| {
+                matches!(
+                    main_axis_sizes(direction, &child.node.state.size).0,
+                    SizeMode::Flex(_)
+                )
+            })
+            .unwrap_or(false)
+    });
+
+    layout_memorizer
+        .lock()
+        .unwrap()
+        .flex_containers
+        .insert(node_data.node.id, has_flex_children);
+
+    has_flex_children
+}
+
 /// Measure the areas of a node's inner children
 #[allow(clippy::too_many_arguments)]
 fn measure_node_children<T>(
@@ -186,27 +558,208 @@ fn measure_node_children<T>(
     font_collection: &mut FontCollection,
     layout_memorizer: &Arc<Mutex<LayoutMemorizer>>,
     must_memorize_layout: bool,
+    alignment: &ChildrenAlignment,
+    measured_children: &mut Vec<MeasuredChild>,
+    containing_area: NodeArea,
+    units: UnitsContext,
 ) {
     match &node_data.node.node_type {
         NodeType::Element { children, tag, .. } => {
+            let size = &node_data.node.state.size;
+            let is_wrapping = size.wrap == WrapMode::Wrap;
+
+            // Area where the current line starts and the size of it's biggest child along the cross axis
+            let line_start = *remaining_inner_area;
+            let initial_inner_sizes = (*inner_width, *inner_height);
+            let mut line_cross_size: f32 = 0.0;
+            let mut line_children = 0;
+
             for child in children {
                 let child_node = node_resolver(child, resolver_options);
 
                 if let Some(child_node) = child_node {
-                    let child_node_area = measure_node_layout::<T>(
-                        &child_node,
-                        *remaining_inner_area,
-                        inner_area,
-                        resolver_options,
-                        layers,
-                        node_resolver,
-                        inherited_relative_layer,
-                        font_collection,
-                        layout_memorizer,
-                        must_memorize_layout,
-                    );
+                    // Absolute children are placed in their containing area and don't take space from their siblings
+                    if child_node.node.state.size.position == PositionMode::Absolute {
+                        measure_positioned_node_layout::<T>(
+                            &child_node,
+                            containing_area,
+                            inner_area,
+                            resolver_options,
+                            layers,
+                            node_resolver,
+                            inherited_relative_layer,
+                            font_collection,
+                            layout_memorizer,
+                            must_memorize_layout,
+                            containing_area,
+                            units,
+                        );
+                        continue;
+                    }
+
+                    let child_index = measured_children.len();
+
+                    // Separate this child from the previous one
+                    if child_index > 0 {
+                        let size = &node_data.node.state.size;
+                        let spacing = alignment.spacing + size.gap_for(size.direction);
+                        match size.direction {
+                            DirectionMode::Vertical => {
+                                remaining_inner_area.y += spacing;
+                                remaining_inner_area.height -= spacing;
+                            }
+                            DirectionMode::Horizontal => {
+                                remaining_inner_area.x += spacing;
+                                remaining_inner_area.width -= spacing;
+                            }
+                            DirectionMode::Both => {}
+                        }
+                    }
+
+                    // Move the child along the cross axis
+                    let mut child_remaining_area = *remaining_inner_area;
+                    if let Some(cross_offset) = alignment.cross_offsets.get(child_index) {
+                        match node_data.node.state.size.direction {
+                            DirectionMode::Vertical => {
+                                child_remaining_area.x += cross_offset;
+                                child_remaining_area.width -= cross_offset;
+                            }
+                            DirectionMode::Horizontal => {
+                                child_remaining_area.y += cross_offset;
+                                child_remaining_area.height -= cross_offset;
+                            }
+                            DirectionMode::Both => {}
+                        }
+                    }
+
+                    // Flex children fill the space that was calculated for them
+                    let flex_main_size = if alignment.measuring {
+                        matches!(
+                            main_axis_sizes(size.direction, &child_node.node.state.size).0,
+                            SizeMode::Flex(_)
+                        )
+                        .then_some(0.0)
+                    } else {
+                        alignment.main_sizes.get(child_index).copied().flatten()
+                    };
+                    if let Some(main_size) = flex_main_size {
+                        match node_data.node.state.size.direction {
+                            DirectionMode::Vertical => {
+                                child_remaining_area.height = main_size;
+                            }
+                            DirectionMode::Horizontal => {
+                                child_remaining_area.width = main_size;
+                            }
+                            DirectionMode::Both => {}
+                        }
+                    }
+
+                    let mut measure_child = |child_remaining_area: NodeArea| {
+                        let mut child_node_area = measure_positioned_node_layout::<T>(
+                            &child_node,
+                            child_remaining_area,
+                            inner_area,
+                            resolver_options,
+                            layers,
+                            node_resolver,
+                            inherited_relative_layer,
+                            font_collection,
+                            layout_memorizer,
+                            must_memorize_layout,
+                            containing_area,
+                            units,
+                        );
+
+                        // Siblings are positioned after the place the child would have without it's offset
+                        if child_node.node.state.size.position == PositionMode::Relative {
+                            let (x, y) = relative_offset(
+                                &child_node.node.state.size,
+                                inner_area,
+                                &units.for_node(&child_node),
+                            );
+                            child_node_area.x -= x;
+                            child_node_area.y -= y;
+                        }
+
+                        // Siblings are positioned after the child's margin
+                        with_margin(child_node_area, child_node.node.state.size.margin)
+                    };
+
+                    let mut child_node_area = measure_child(child_remaining_area);
+
+                    // Move the child to a new line if it doesn't fit in the current one
+                    if is_wrapping
+                        && line_children > 0
+                        && overflows_line(
+                            size.direction,
+                            remaining_inner_area,
+                            &line_start,
+                            &child_node_area,
+                        )
+                    {
+                        match size.direction {
+                            DirectionMode::Horizontal => {
+                                let line_size = line_cross_size + size.row_gap;
+                                remaining_inner_area.x = line_start.x;
+                                remaining_inner_area.width = line_start.width;
+                                remaining_inner_area.y += line_size;
+                                remaining_inner_area.height -= line_size;
+                            }
+                            DirectionMode::Vertical => {
+                                let line_size = line_cross_size + size.column_gap;
+                                remaining_inner_area.y = line_start.y;
+                                remaining_inner_area.height = line_start.height;
+                                remaining_inner_area.x += line_size;
+                                remaining_inner_area.width -= line_size;
+                            }
+                            DirectionMode::Both => {}
+                        }
+                        line_cross_size = 0.0;
+                        line_children = 0;
+
+                        // Measure again unless the memorized layout already starts the new line
+                        if child_node_area.x != remaining_inner_area.x
+                            || child_node_area.y != remaining_inner_area.y
+                        {
+                            layout_memorizer
+                                .lock()
+                                .unwrap()
+                                .mark_as_dirty(child_node.node.id);
+                            child_node_area = measure_child(*remaining_inner_area);
+                        }
+                    }
+
+                    measured_children.push(MeasuredChild {
+                        area: child_node_area,
+                        size: child_node.node.state.size.clone(),
+                        units: units.for_node(&child_node),
+                    });
 
                     match node_data.node.state.size.direction {
+                        DirectionMode::Vertical if is_wrapping => {
+                            remaining_inner_area.height -= child_node_area.height;
+                            remaining_inner_area.y = child_node_area.y + child_node_area.height;
+                            line_cross_size = line_cross_size.max(child_node_area.width);
+                            line_children += 1;
+
+                            // Save the tallest line and accumulate all the lines widths
+                            *inner_height = inner_height.max(remaining_inner_area.y - line_start.y);
+                            *inner_width = initial_inner_sizes.0
+                                + (remaining_inner_area.x - line_start.x)
+                                + line_cross_size;
+                        }
+                        DirectionMode::Horizontal if is_wrapping => {
+                            remaining_inner_area.width -= child_node_area.width;
+                            remaining_inner_area.x = child_node_area.x + child_node_area.width;
+                            line_cross_size = line_cross_size.max(child_node_area.height);
+                            line_children += 1;
+
+                            // Save the widest line and accumulate all the lines heights
+                            *inner_width = inner_width.max(remaining_inner_area.x - line_start.x);
+                            *inner_height = initial_inner_sizes.1
+                                + (remaining_inner_area.y - line_start.y)
+                                + line_cross_size;
+                        }
                         DirectionMode::Vertical => {
                             remaining_inner_area.height -= child_node_area.height;
                             remaining_inner_area.y = child_node_area.y + child_node_area.height;
@@ -256,14 +809,27 @@ fn measure_node_children<T>(
                     }
                 }
             }
+
+            // Close the last line so the node can grow to fit it
+            if is_wrapping {
+                match size.direction {
+                    DirectionMode::Horizontal => {
+                        remaining_inner_area.y += line_cross_size;
+                        remaining_inner_area.height -= line_cross_size;
+                    }
+                    DirectionMode::Vertical => {
+                        remaining_inner_area.x += line_cross_size;
+                        remaining_inner_area.width -= line_cross_size;
+                    }
+                    DirectionMode::Both => {}
+                }
+            }
+
             if tag == "paragraph"
                 && CursorMode::Editable == node_data.node.state.cursor_settings.mode
             {
-                let font_size = node_data.node.state.font_style.font_size;
-                let font_family = &node_data.node.state.font_style.font_family;
                 let align = node_data.node.state.font_style.align;
                 let max_lines = node_data.node.state.font_style.max_lines;
-                let font_style = node_data.node.state.font_style.font_style;
 
                 let mut paragraph_style = ParagraphStyle::default();
                 paragraph_style.set_text_align(align);
@@ -273,24 +839,18 @@ fn measure_node_children<T>(
                 let mut paragraph_builder =
                     ParagraphBuilder::new(&paragraph_style, font_collection.clone());
 
-                paragraph_builder.push_style(
-                    TextStyle::new()
-                        .set_font_style(font_style)
-                        .set_font_size(font_size)
-                        .set_font_families(&[font_family]),
-                );
+                paragraph_builder.push_style(&node_data.node.state.font_style.text_style());
 
                 let texts = get_inner_texts(children, node_resolver, resolver_options);
 
                 for node_text in texts {
                     paragraph_builder.push_style(
-                        TextStyle::new()
-                            .set_font_style(node_text.0.font_style.font_style)
+                        node_text
+                            .0
+                            .font_style
+                            .text_style()
                             .set_height_override(true)
-                            .set_height(node_text.0.font_style.line_height)
-                            .set_color(node_text.0.font_style.color)
-                            .set_font_size(node_text.0.font_style.font_size)
-                            .set_font_families(&[node_text.0.font_style.font_family.clone()]),
+                            .set_height(node_text.0.font_style.line_height),
                     );
                     paragraph_builder.add_text(node_text.1.clone());
                 }
@@ -298,7 +858,9 @@ fn measure_node_children<T>(
                 let mut paragraph = paragraph_builder.build();
                 paragraph.layout(node_area.width);
 
-                if let Some((cursor_ref, cursor_id, positions)) = get_cursor(node_data) {
+                // Only notify about the final layout
+                let cursor = get_cursor(node_data).filter(|_| must_memorize_layout);
+                if let Some((cursor_ref, cursor_id, positions)) = cursor {
                     // Calculate the new cursor position
                     let char_position = paragraph.get_glyph_position_at_coordinate(positions);
 
@@ -313,10 +875,8 @@ fn measure_node_children<T>(
         NodeType::Text { text } => {
             let line_height = node_data.node.state.font_style.line_height;
             let font_size = node_data.node.state.font_style.font_size;
-            let font_family = &node_data.node.state.font_style.font_family;
             let align = node_data.node.state.font_style.align;
             let max_lines = node_data.node.state.font_style.max_lines;
-            let font_style = node_data.node.state.font_style.font_style;
 
             let mut paragraph_style = ParagraphStyle::default();
             paragraph_style.set_text_align(align);
@@ -326,12 +886,7 @@ fn measure_node_children<T>(
             let mut paragraph_builder =
                 ParagraphBuilder::new(&paragraph_style, font_collection.clone());
 
-            paragraph_builder.push_style(
-                TextStyle::new()
-                    .set_font_style(font_style)
-                    .set_font_size(font_size)
-                    .set_font_families(&[font_family]),
-            );
+            paragraph_builder.push_style(&node_data.node.state.font_style.text_style());
 
             paragraph_builder.add_text(text);
 
@@ -346,6 +901,248 @@ fn measure_node_children<T>(
     }
 }
 
+/// Resolve the size of every track of a grid, fixed tracks are resolved against the inner area
+/// and the rest of space is divided among the `flex` and `auto` tracks by their weight
+fn resolve_tracks(
+    tracks: &[SizeMode],
+    count: usize,
+    inner_size: f32,
+    gap: f32,
+    units: &UnitsContext,
+) -> Vec<f32> {
+    let tracks = (0..count)
+        .map(|i| tracks.get(i).unwrap_or(&SizeMode::Auto))
+        .collect::<Vec<&SizeMode>>();
+
+    let weight = |track: &SizeMode| match track {
+        SizeMode::Flex(weight) => Some(*weight),
+        SizeMode::Auto => Some(1.0),
+        _ => None,
+    };
+
+    let fixed_size: f32 = tracks
+        .iter()
+        .filter_map(|track| resolve_limit(track, inner_size, units))
+        .sum();
+    let total_weight: f32 = tracks.iter().filter_map(|track| weight(track)).sum();
+    let total_gap = gap * count.saturating_sub(1) as f32;
+    let free_space = (inner_size - total_gap - fixed_size).max(0.0);
+
+    tracks
+        .iter()
+        .map(|track| match weight(track) {
+            Some(weight) if total_weight > 0.0 => free_space * weight / total_weight,
+            Some(_) => 0.0,
+            None => resolve_limit(track, inner_size, units).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Position of a child inside a grid, as `(column, row, column span, row span)`
+type GridCell = (usize, usize, usize, usize);
+
+/// Place every child in a cell of the grid, children without a `grid_column` or `grid_row`
+/// fill the first free cells in which they fit, row by row
+fn place_grid_children(columns: usize, sizes: &[&Size]) -> Vec<GridCell> {
+    let mut occupied: Vec<Vec<bool>> = Vec::new();
+
+    let fits = |occupied: &Vec<Vec<bool>>, column: usize, row: usize, spans: (usize, usize)| {
+        (row..row + spans.1).all(|row| {
+            (column..column + spans.0).all(|column| {
+                !occupied
+                    .get(row)
+                    .and_then(|cells| cells.get(column))
+                    .copied()
+                    .unwrap_or(false)
+            })
+        })
+    };
+
+    sizes
+        .iter()
+        .map(|size| {
+            let column_span = size.grid_column_span.clamp(1, columns);
+            let row_span = size.grid_row_span.max(1);
+            let spans = (column_span, row_span);
+            let last_column = columns - column_span;
+
+            let (column, row) = match (size.grid_column, size.grid_row) {
+                (Some(column), Some(row)) => (column.min(last_column), row),
+                (Some(column), None) => {
+                    let column = column.min(last_column);
+                    let row = (0..)
+                        .find(|row| fits(&occupied, column, *row, spans))
+                        .unwrap();
+                    (column, row)
+                }
+                (None, Some(row)) => {
+                    let column = (0..=last_column)
+                        .find(|column| fits(&occupied, *column, row, spans))
+                        .unwrap_or(0);
+                    (column, row)
+                }
+                (None, None) => (0..)
+                    .find_map(|row| {
+                        (0..=last_column)
+                            .find(|column| fits(&occupied, *column, row, spans))
+                            .map(|column| (column, row))
+                    })
+                    .unwrap(),
+            };
+
+            for row in row..row + row_span {
+                if occupied.len() <= row {
+                    occupied.resize(row + 1, vec![false; columns]);
+                }
+                for cell in &mut occupied[row][column..column + column_span] {
+                    *cell = true;
+                }
+            }
+
+            (column, row, column_span, row_span)
+        })
+        .collect()
+}
+
+/// Offset of every track from the start of the grid
+fn track_offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+    sizes
+        .iter()
+        .scan(0.0, |offset, size| {
+            let track_offset = *offset;
+            *offset += size + gap;
+            Some(track_offset)
+        })
+        .collect()
+}
+
+/// Size of the tracks from `start` to `start + span`, including the gaps between them
+fn span_size(sizes: &[f32], start: usize, span: usize, gap: f32) -> f32 {
+    sizes[start..start + span].iter().sum::<f32>() + gap * (span - 1) as f32
+}
+
+/// Measure the areas of the children of a `grid` node, every child takes the area of it's cell
+#[allow(clippy::too_many_arguments)]
+fn measure_grid_children<T>(
+    node_data: &NodeData,
+    layers: &mut Layers,
+    remaining_inner_area: &mut NodeArea,
+    inner_area: NodeArea,
+    resolver_options: &mut T,
+    node_resolver: NodeResolver<T>,
+    inner_height: &mut f32,
+    inner_width: &mut f32,
+    inherited_relative_layer: i16,
+    font_collection: &mut FontCollection,
+    layout_memorizer: &Arc<Mutex<LayoutMemorizer>>,
+    must_memorize_layout: bool,
+    containing_area: NodeArea,
+    units: UnitsContext,
+) {
+    if let NodeType::Element { children, .. } = &node_data.node.node_type {
+        let size = &node_data.node.state.size;
+
+        let mut grid_children = Vec::new();
+        for child in children {
+            if let Some(child_node) = node_resolver(child, resolver_options) {
+                // Absolute children are placed in their containing area and don't take any cell
+                if child_node.node.state.size.position == PositionMode::Absolute {
+                    measure_positioned_node_layout::<T>(
+                        &child_node,
+                        containing_area,
+                        inner_area,
+                        resolver_options,
+                        layers,
+                        node_resolver,
+                        inherited_relative_layer,
+                        font_collection,
+                        layout_memorizer,
+                        must_memorize_layout,
+                        containing_area,
+                        units,
+                    );
+                } else {
+                    grid_children.push(child_node);
+                }
+            }
+        }
+
+        let columns = size.grid_columns.len().max(1);
+        let cells = place_grid_children(
+            columns,
+            &grid_children
+                .iter()
+                .map(|child| &child.node.state.size)
+                .collect::<Vec<&Size>>(),
+        );
+        let rows = cells
+            .iter()
+            .map(|(_, row, _, row_span)| row + row_span)
+            .max()
+            .unwrap_or(0)
+            .max(size.grid_rows.len());
+
+        let column_sizes = resolve_tracks(
+            &size.grid_columns,
+            columns,
+            inner_area.width,
+            size.column_gap,
+            &units,
+        );
+        let row_sizes = resolve_tracks(
+            &size.grid_rows,
+            rows,
+            inner_area.height,
+            size.row_gap,
+            &units,
+        );
+        let column_offsets = track_offsets(&column_sizes, size.column_gap);
+        let row_offsets = track_offsets(&row_sizes, size.row_gap);
+
+        let grid_start = *remaining_inner_area;
+
+        for (child_node, (column, row, column_span, row_span)) in grid_children.iter().zip(cells) {
+            let cell_area = NodeArea {
+                x: grid_start.x + column_offsets[column],
+                y: grid_start.y + row_offsets[row],
+                width: span_size(&column_sizes, column, column_span, size.column_gap),
+                height: span_size(&row_sizes, row, row_span, size.row_gap),
+            };
+
+            measure_positioned_node_layout::<T>(
+                child_node,
+                cell_area,
+                cell_area,
+                resolver_options,
+                layers,
+                node_resolver,
+                inherited_relative_layer,
+                font_collection,
+                layout_memorizer,
+                must_memorize_layout,
+                containing_area,
+                units,
+            );
+        }
+
+        let grid_width = span_size(&column_sizes, 0, columns, size.column_gap);
+        let grid_height = if rows > 0 {
+            span_size(&row_sizes, 0, rows, size.row_gap)
+        } else {
+            0.0
+        };
+
+        *inner_width += grid_width;
+        *inner_height += grid_height;
+
+        // Move the remaining area to the end of the grid so `auto` sized nodes fit it
+        remaining_inner_area.x = grid_start.x + grid_width;
+        remaining_inner_area.y = grid_start.y + grid_height;
+        remaining_inner_area.width = grid_start.width - grid_width;
+        remaining_inner_area.height = grid_start.height - grid_height;
+    }
+}
+
 fn get_inner_texts<T>(
     children: &[ElementId],
     node_resolver: NodeResolver<T>,
@@ -413,6 +1210,76 @@ pub fn measure_node_layout<T>(
     layout_memorizer: &Arc<Mutex<LayoutMemorizer>>,
     must_memorize_layout: bool,
 ) -> NodeArea {
+    // Measurements from previous passes might be outdated
+    layout_memorizer.lock().unwrap().measurements.clear();
+
+    measure_positioned_node_layout(
+        node_data,
+        remaining_area,
+        parent_area,
+        resolver_options,
+        layers,
+        node_resolver,
+        inherited_relative_layer,
+        font_collection,
+        layout_memorizer,
+        must_memorize_layout,
+        parent_area,
+        UnitsContext {
+            viewport: parent_area,
+            font_size: node_data.node.state.font_style.font_size,
+            root_font_size: node_data.node.state.font_style.font_size,
+        },
+    )
+}
+
+/// Measure an area of a given Node, `absolute` nodes are placed inside `containing_area`,
+/// which is the area of their nearest positioned ancestor
+#[allow(clippy::too_many_arguments)]
+fn measure_positioned_node_layout<T>(
+    node_data: &NodeData,
+    remaining_area: NodeArea,
+    parent_area: NodeArea,
+    resolver_options: &mut T,
+    layers: &mut Layers,
+    node_resolver: NodeResolver<T>,
+    inherited_relative_layer: i16,
+    font_collection: &mut FontCollection,
+    layout_memorizer: &Arc<Mutex<LayoutMemorizer>>,
+    must_memorize_layout: bool,
+    containing_area: NodeArea,
+    units: UnitsContext,
+) -> NodeArea {
+    let units = units.for_node(node_data);
+
+    // Nodes that are measured without memorizing them are only measured once per size of the available area,
+    // and then just moved to wherever they are measured again
+    let measurement_key = (!must_memorize_layout
+        && node_data.node.state.size.position != PositionMode::Absolute)
+        .then(|| {
+            MeasurementKey::new(
+                node_data.node.id,
+                &remaining_area,
+                &parent_area,
+                &containing_area,
+            )
+        });
+    if let Some(measurement_key) = &measurement_key {
+        let measurement = layout_memorizer
+            .lock()
+            .unwrap()
+            .measurements
+            .get(measurement_key)
+            .copied();
+        if let Some(measurement) = measurement {
+            return NodeArea {
+                x: remaining_area.x + measurement.x,
+                y: remaining_area.y + measurement.y,
+                ..measurement
+            };
+        }
+    }
+
     // Caculate the corresponding layer of this node
     let (node_layer, inherited_relative_layer) =
         layers.calculate_layer(node_data, inherited_relative_layer);
@@ -452,7 +1319,30 @@ pub fn measure_node_layout<T>(
 
     let (mut node_area, mut remaining_inner_area, inner_area, (mut inner_width, mut inner_height)) =
         if must_recalculate {
-            let node_area = calculate_area(node_data, remaining_area, parent_area);
+            let size = &node_data.node.state.size;
+            let node_area = match size.position {
+                PositionMode::Absolute => {
+                    let mut node_area = calculate_area(
+                        node_data,
+                        absolute_area(size, containing_area, &units),
+                        containing_area,
+                        &units,
+                    );
+                    anchor_absolute_area(size, &mut node_area, containing_area, &units);
+                    node_area
+                }
+                PositionMode::Relative => {
+                    let mut node_area =
+                        calculate_area(node_data, remaining_area, parent_area, &units);
+                    let (x, y) = relative_offset(size, parent_area, &units);
+                    node_area.x += x;
+                    node_area.y += y;
+                    node_area
+                }
+                PositionMode::Static => {
+                    calculate_area(node_data, remaining_area, parent_area, &units)
+                }
+            };
 
             // Returns a tuple, the first element is the layer in which the current node must be added
             // and the second indicates the layer that it's children must inherit
@@ -509,6 +1399,13 @@ pub fn measure_node_layout<T>(
             (area, remaining_inner_area, inner_area, inner_sizes)
         };
 
+    // Positioned nodes become the containing area of their absolute descendants
+    let containing_area = if node_data.node.state.size.position == PositionMode::Static {
+        containing_area
+    } else {
+        node_area
+    };
+
     // Re calculate the children layouts after the parent has properly adjusted it's size and axis according to it's children
     if DisplayMode::Center == node_data.node.state.style.display {
         measure_node_children(
@@ -525,6 +1422,10 @@ pub fn measure_node_layout<T>(
             font_collection,
             layout_memorizer,
             false, // By specifying `false` in the argument `must_memorize_layout` it will not cache any inner children layout in this first iteration
+            &ChildrenAlignment::default(),
+            &mut Vec::new(),
+            containing_area,
+            units,
         );
 
         let space_left_vertically = (inner_area.height - inner_height) / 2.0;
@@ -549,21 +1450,107 @@ pub fn measure_node_layout<T>(
         }
     }
 
-    measure_node_children(
-        node_data,
-        &mut node_area,
-        layers,
-        &mut remaining_inner_area,
-        inner_area,
-        resolver_options,
-        node_resolver,
-        &mut inner_height,
-        &mut inner_width,
-        inherited_relative_layer,
-        font_collection,
-        layout_memorizer,
-        must_memorize_layout,
-    );
+    let size = &node_data.node.state.size;
+    let is_grid = DisplayMode::Grid == node_data.node.state.style.display;
+    let is_aligned = !is_grid
+        && size.direction != DirectionMode::Both
+        && size.wrap == WrapMode::NoWrap
+        && (size.main_align != AlignmentMode::Start
+            || size.cross_align != AlignmentMode::Start
+            || has_flex_children(
+                node_data,
+                must_recalculate,
+                resolver_options,
+                node_resolver,
+                layout_memorizer,
+            ));
+
+    // Measure the children first to know how they must be sized and aligned
+    let alignment = if is_aligned {
+        let mut measured_children = Vec::new();
+        let mut measured_node_area = node_area;
+        let mut measured_remaining_inner_area = remaining_inner_area;
+        let (mut measured_inner_height, mut measured_inner_width) = (inner_height, inner_width);
+        measure_node_children(
+            node_data,
+            &mut measured_node_area,
+            layers,
+            &mut measured_remaining_inner_area,
+            inner_area,
+            resolver_options,
+            node_resolver,
+            &mut measured_inner_height,
+            &mut measured_inner_width,
+            inherited_relative_layer,
+            font_collection,
+            layout_memorizer,
+            false,
+            &ChildrenAlignment {
+                measuring: true,
+                ..ChildrenAlignment::default()
+            },
+            &mut measured_children,
+            containing_area,
+            units,
+        );
+
+        let alignment = align_children(node_data, inner_area, &measured_children);
+
+        match size.direction {
+            DirectionMode::Vertical => {
+                remaining_inner_area.y += alignment.main_offset;
+                remaining_inner_area.height -= alignment.main_offset;
+            }
+            DirectionMode::Horizontal => {
+                remaining_inner_area.x += alignment.main_offset;
+                remaining_inner_area.width -= alignment.main_offset;
+            }
+            DirectionMode::Both => {}
+        }
+
+        alignment
+    } else {
+        ChildrenAlignment::default()
+    };
+
+    if is_grid {
+        measure_grid_children(
+            node_data,
+            layers,
+            &mut remaining_inner_area,
+            inner_area,
+            resolver_options,
+            node_resolver,
+            &mut inner_height,
+            &mut inner_width,
+            inherited_relative_layer,
+            font_collection,
+            layout_memorizer,
+            must_memorize_layout,
+            containing_area,
+            units,
+        );
+    } else {
+        measure_node_children(
+            node_data,
+            &mut node_area,
+            layers,
+            &mut remaining_inner_area,
+            inner_area,
+            resolver_options,
+            node_resolver,
+            &mut inner_height,
+            &mut inner_width,
+            inherited_relative_layer,
+            font_collection,
+            layout_memorizer,
+            must_memorize_layout,
+            &alignment,
+            &mut Vec::new(),
+            containing_area,
+            units,
+        );
+    }
 
     if must_recalculate && must_memorize_layout {
         layout_memorizer
@@ -574,12 +1561,14 @@ pub fn measure_node_layout<T>(
 
     match &node_data.node.node_type {
         NodeType::Text { .. } => {}
+        // Nodes with an aspect ratio don't grow to fit their children
+        _ if node_data.node.state.size.aspect_ratio.is_some() => {}
         _ => {
             if let SizeMode::Auto = node_data.node.state.size.width {
                 node_area.width = remaining_inner_area.x - node_area.x + padding.1;
             }
             if let SizeMode::Auto = node_data.node.state.size.height {
-                node_area.height = remaining_inner_area.y - node_area.y + padding.0;
+                node_area.height = remaining_inner_area.y - node_area.y + padding.2;
             }
         }
     }
@@ -589,8 +1578,15 @@ pub fn measure_node_layout<T>(
         layers.add_element(node_data, &node_area, node_layer);
     }
 
-    // Asynchronously notify the Node's reference about the new size layout
-    if let Some(reference) = &node_data.node.state.references.node_ref {
+    // Asynchronously notify the Node's reference about the new size layout, but not about the previous measurements
+    let reference = node_data
+        .node
+        .state
+        .references
+        .node_ref
+        .as_ref()
+        .filter(|_| must_memorize_layout);
+    if let Some(reference) = reference {
         reference
             .send(NodeReferenceLayout {
                 x: node_area.x,
@@ -603,5 +1599,16 @@ pub fn measure_node_layout<T>(
             .ok();
     }
 
+    if let Some(measurement_key) = measurement_key {
+        layout_memorizer.lock().unwrap().measurements.insert(
+            measurement_key,
+            NodeArea {
+                x: node_area.x - remaining_area.x,
+                y: node_area.y - remaining_area.y,
+                ..node_area
+            },
+        );
+    }
+
     node_area
 }
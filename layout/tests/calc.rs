@@ -1,16 +1,17 @@
-use freya_layout::run_calculations;
-use freya_node_state::CalcType;
+use freya_common::NodeArea;
+use freya_layout::{run_calculations, UnitsContext};
+use freya_node_state::{parse_calc, CalcType};
 
 #[test]
 fn works_per_add_man() {
     // 50% + 50 = 250 + 50 = 300
     let result = run_calculations(
-        &vec![
-            CalcType::Percentage(50.0),
-            CalcType::Add,
-            CalcType::Manual(50.0),
-        ],
+        &CalcType::Add(
+            Box::new(CalcType::Percentage(50.0)),
+            Box::new(CalcType::Manual(50.0)),
+        ),
         500.0,
+        &UnitsContext::default(),
     );
     assert_eq!(result, 300.0);
 }
@@ -19,14 +20,9 @@ fn works_per_add_man() {
 fn works_per_sub_man_sum_per() {
     //10% - 25 + 75% = 50 - 25 + 375 = 400
     let result = run_calculations(
-        &vec![
-            CalcType::Percentage(10.0),
-            CalcType::Sub,
-            CalcType::Manual(25.0),
-            CalcType::Add,
-            CalcType::Percentage(75.0),
-        ],
+        &parse_calc("calc(10% - 25 + 75%)").unwrap(),
         500.0,
+        &UnitsContext::default(),
     );
     assert_eq!(result, 400.0);
 }
@@ -35,14 +31,91 @@ fn works_per_sub_man_sum_per() {
 fn works_man_div_per_mul_man() {
     // 1000 / 50% * 25 = 1000 / 250 * 25 = 100
     let result = run_calculations(
-        &vec![
-            CalcType::Manual(1000.0),
-            CalcType::Div,
-            CalcType::Percentage(50.0),
-            CalcType::Mul,
-            CalcType::Manual(25.0),
-        ],
+        &parse_calc("calc(1000 / 50% * 25)").unwrap(),
         500.0,
+        &UnitsContext::default(),
     );
     assert_eq!(result, 100.0);
 }
+
+#[test]
+fn works_precedence() {
+    // 100% - 20 * 2 = 500 - 40 = 460
+    let result = run_calculations(
+        &parse_calc("calc(100% - 20 * 2)").unwrap(),
+        500.0,
+        &UnitsContext::default(),
+    );
+    assert_eq!(result, 460.0);
+}
+
+#[test]
+fn works_parentheses() {
+    // (100% - 20) / 3 = 480 / 3 = 160
+    let result = run_calculations(
+        &parse_calc("calc((100% - 20) / 3)").unwrap(),
+        500.0,
+        &UnitsContext::default(),
+    );
+    assert_eq!(result, 160.0);
+}
+
+#[test]
+fn works_unary_minus() {
+    // -(10% - 100) * 2 = -(50 - 100) * 2 = 100
+    let result = run_calculations(
+        &parse_calc("calc(-(10% - 100) * 2)").unwrap(),
+        500.0,
+        &UnitsContext::default(),
+    );
+    assert_eq!(result, 100.0);
+}
+
+#[test]
+fn works_min_max() {
+    // min(50%, 200) + max(10, 5%) = 200 + 25 = 225
+    let result = run_calculations(
+        &parse_calc("calc(min(50%, 200) + max(10, 5%))").unwrap(),
+        500.0,
+        &UnitsContext::default(),
+    );
+    assert_eq!(result, 225.0);
+}
+
+#[test]
+fn works_clamp() {
+    let calc = parse_calc("calc(clamp(100, 50%, 400))").unwrap();
+    assert_eq!(
+        run_calculations(&calc, 100.0, &UnitsContext::default()),
+        100.0
+    );
+    assert_eq!(
+        run_calculations(&calc, 500.0, &UnitsContext::default()),
+        250.0
+    );
+    assert_eq!(
+        run_calculations(&calc, 1000.0, &UnitsContext::default()),
+        400.0
+    );
+}
+
+#[test]
+fn works_relative_units() {
+    let units = UnitsContext {
+        viewport: NodeArea {
+            x: 0.0,
+            y: 0.0,
+            width: 1000.0,
+            height: 500.0,
+        },
+        font_size: 20.0,
+        root_font_size: 10.0,
+    };
+    // 10vw + 10vh - 2em + 3rem = 100 + 50 - 40 + 30 = 140
+    let result = run_calculations(
+        &parse_calc("calc(10vw + 10vh - 2em + 3rem)").unwrap(),
+        500.0,
+        &units,
+    );
+    assert_eq!(result, 140.0);
+}
@@ -5,7 +5,10 @@ use dioxus_native_core::real_dom::{Node, NodeType};
 use freya_common::{LayoutMemorizer, NodeArea};
 use freya_layers::{Layers, NodeData};
 use freya_layout::measure_node_layout;
-use freya_node_state::{DirectionMode, NodeState, Size, SizeMode};
+use freya_node_state::{
+    AlignmentMode, CalcType, DirectionMode, DisplayMode, NodeState, PositionMode, Size, SizeMode,
+    WrapMode,
+};
 use lazy_static::lazy_static;
 use skia_safe::textlayout::FontCollection;
 
@@ -210,6 +213,779 @@ fn x_y() {
     assert_eq!(result.y, 25.0);
 }
 
+#[test]
+fn main_align_center_cross_align_end() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Vertical,
+        main_align: AlignmentMode::Center,
+        cross_align: AlignmentMode::End,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| square_child(id.0),
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let first = get_area(&layers, 1);
+    let second = get_area(&layers, 2);
+
+    assert_eq!((first.x, first.y), (150.0, 100.0));
+    assert_eq!((second.x, second.y), (150.0, 150.0));
+}
+
+fn centered_child(id: usize) -> Option<NodeData> {
+    if id == 40 {
+        return square_child(id);
+    }
+    Some(NodeData {
+        node: Node {
+            id: ElementId(id),
+            parent: None,
+            state: NodeState::default().set_size(Size {
+                direction: DirectionMode::Vertical,
+                main_align: AlignmentMode::Center,
+                cross_align: AlignmentMode::Center,
+                ..expanded_size()
+            }),
+            node_type: NodeType::Element {
+                tag: "rect".to_string(),
+                namespace: None,
+                children: vec![ElementId(id + 1)],
+            },
+            height: 1,
+        },
+    })
+}
+
+#[test]
+fn nested_alignment() {
+    let node = centered_child(1).unwrap().node;
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| centered_child(id.0),
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    // Measuring every aligned node twice on every level would never finish
+    let square = get_area(&layers, 40);
+    assert_eq!((square.x, square.y), (75.0, 125.0));
+}
+
+#[test]
+fn main_align_space_between_cross_align_center() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Horizontal,
+        main_align: AlignmentMode::SpaceBetween,
+        cross_align: AlignmentMode::Center,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| square_child(id.0),
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let first = get_area(&layers, 1);
+    let second = get_area(&layers, 2);
+
+    assert_eq!((first.x, first.y), (0.0, 125.0));
+    assert_eq!((second.x, second.y), (150.0, 125.0));
+}
+
+#[test]
+fn gap() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Vertical,
+        row_gap: 10.0,
+        column_gap: 20.0,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2), ElementId(3)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| square_child(id.0),
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    assert_eq!(get_area(&layers, 1).y, 0.0);
+    assert_eq!(get_area(&layers, 2).y, 60.0);
+    assert_eq!(get_area(&layers, 3).y, 120.0);
+}
+
+#[test]
+fn margin() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Vertical,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| {
+            let mut child = square_child(id.0)?;
+            if id.0 == 1 {
+                child.node.state.size.margin = (10.0, 0.0, 5.0, 20.0);
+            }
+            Some(child)
+        },
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let first = get_area(&layers, 1);
+    let second = get_area(&layers, 2);
+
+    assert_eq!((first.x, first.y, first.width), (20.0, 10.0, 50.0));
+    assert_eq!((second.x, second.y), (0.0, 65.0));
+}
+
+#[test]
+fn wrap() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Horizontal,
+        wrap: WrapMode::Wrap,
+        row_gap: 10.0,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: (1..=5).map(ElementId).collect(),
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| square_child(id.0),
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let fourth = get_area(&layers, 4);
+    let fifth = get_area(&layers, 5);
+
+    assert_eq!((fourth.x, fourth.y), (150.0, 0.0));
+    assert_eq!((fifth.x, fifth.y), (0.0, 60.0));
+}
+
+fn flex_child(id: usize) -> Option<NodeData> {
+    let width = match id {
+        1 => SizeMode::Manual(50.0),
+        2 => SizeMode::Flex(1.0),
+        _ => SizeMode::Flex(3.0),
+    };
+    let max_width = match id {
+        4 => SizeMode::Manual(20.0),
+        _ => SizeMode::Auto,
+    };
+    Some(NodeData {
+        node: Node {
+            id: ElementId(id),
+            parent: None,
+            state: NodeState::default().set_size(Size {
+                width,
+                max_width,
+                height: SizeMode::Manual(50.0),
+                ..expanded_size()
+            }),
+            node_type: NodeType::Element {
+                tag: "rect".to_string(),
+                namespace: None,
+                children: Vec::new(),
+            },
+            height: 1,
+        },
+    })
+}
+
+#[test]
+fn flex() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Horizontal,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2), ElementId(3)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| flex_child(id.0),
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let fixed = get_area(&layers, 1);
+    let small = get_area(&layers, 2);
+    let big = get_area(&layers, 3);
+
+    assert_eq!((fixed.x, fixed.width), (0.0, 50.0));
+    assert_eq!((small.x, small.width), (50.0, 37.5));
+    assert_eq!((big.x, big.width), (87.5, 112.5));
+}
+
+#[test]
+fn flex_max_size() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Horizontal,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(4), ElementId(3)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| flex_child(id.0),
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let limited = get_area(&layers, 4);
+    let big = get_area(&layers, 3);
+
+    assert_eq!((limited.x, limited.width), (50.0, 20.0));
+    assert_eq!((big.x, big.width), (70.0, 130.0));
+}
+
+#[test]
+fn flex_auto_sibling() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Horizontal,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2), ElementId(5)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| match id.0 {
+            // Auto sized, it's as wide as it's child
+            5 => {
+                let mut child = square_child(5)?;
+                child.node.state.size.width = SizeMode::Auto;
+                child.node.state.size.direction = DirectionMode::Horizontal;
+                child.node.node_type = NodeType::Element {
+                    tag: "rect".to_string(),
+                    namespace: None,
+                    children: vec![ElementId(6)],
+                };
+                Some(child)
+            }
+            // Fills the available space up to 40
+            6 => {
+                let mut child = square_child(6)?;
+                child.node.state.size.width = SizeMode::Percentage(100.0);
+                child.node.state.size.max_width = SizeMode::Manual(40.0);
+                Some(child)
+            }
+            id => flex_child(id),
+        },
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let flex = get_area(&layers, 2);
+    let auto = get_area(&layers, 5);
+
+    assert_eq!((flex.x, flex.width), (50.0, 110.0));
+    assert_eq!((auto.x, auto.width), (160.0, 40.0));
+}
+
+#[test]
+fn flex_zero() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Horizontal,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2), ElementId(5)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| match id.0 {
+            2 => {
+                let mut child = flex_child(2)?;
+                child.node.state.size.width = SizeMode::Flex(0.0);
+                Some(child)
+            }
+            5 => square_child(5),
+            id => flex_child(id),
+        },
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let empty = get_area(&layers, 2);
+    let square = get_area(&layers, 5);
+
+    assert_eq!((empty.x, empty.width), (50.0, 0.0));
+    assert_eq!((square.x, square.width), (50.0, 50.0));
+}
+
+#[test]
+fn position() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Vertical,
+        position: PositionMode::Relative,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2), ElementId(3)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| {
+            let mut child = square_child(id.0)?;
+            let size = &mut child.node.state.size;
+            match id.0 {
+                1 => {
+                    size.position = PositionMode::Relative;
+                    size.top = SizeMode::Manual(5.0);
+                    size.left = SizeMode::Percentage(10.0);
+                }
+                2 => {
+                    size.position = PositionMode::Absolute;
+                    size.right = SizeMode::Manual(10.0);
+                    size.bottom = SizeMode::Manual(20.0);
+                }
+                _ => {}
+            }
+            Some(child)
+        },
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let relative = get_area(&layers, 1);
+    let absolute = get_area(&layers, 2);
+    let sibling = get_area(&layers, 3);
+
+    assert_eq!((relative.x, relative.y), (20.0, 5.0));
+    assert_eq!((absolute.x, absolute.y), (140.0, 230.0));
+    assert_eq!((sibling.x, sibling.y), (0.0, 50.0));
+}
+
+#[test]
+fn grid() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        grid_columns: vec![
+            SizeMode::Manual(100.0),
+            SizeMode::Flex(1.0),
+            SizeMode::Flex(1.0),
+        ],
+        grid_rows: vec![SizeMode::Manual(50.0), SizeMode::Auto],
+        row_gap: 10.0,
+        column_gap: 10.0,
+        ..expanded_size()
+    });
+    node.state.style.display = DisplayMode::Grid;
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2), ElementId(3)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| {
+            let mut child = square_child(id.0)?;
+            let size = &mut child.node.state.size;
+            size.width = SizeMode::Percentage(100.0);
+            size.height = SizeMode::Percentage(100.0);
+            match id.0 {
+                1 => {
+                    size.grid_column_span = 2;
+                }
+                3 => {
+                    size.grid_column = Some(1);
+                    size.grid_row = Some(1);
+                }
+                _ => {}
+            }
+            Some(child)
+        },
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let spanned = get_area(&layers, 1);
+    let auto_placed = get_area(&layers, 2);
+    let placed = get_area(&layers, 3);
+
+    assert_eq!(
+        (spanned.x, spanned.y, spanned.width, spanned.height),
+        (0.0, 0.0, 150.0, 50.0)
+    );
+    assert_eq!(
+        (auto_placed.x, auto_placed.y, auto_placed.width),
+        (160.0, 0.0, 40.0)
+    );
+    assert_eq!(
+        (placed.x, placed.y, placed.width, placed.height),
+        (110.0, 60.0, 40.0, 240.0)
+    );
+}
+
+#[test]
+fn aspect_ratio() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        direction: DirectionMode::Vertical,
+        ..expanded_size()
+    });
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1), ElementId(2)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| {
+            let mut child = square_child(id.0)?;
+            let size = &mut child.node.state.size;
+            match id.0 {
+                1 => {
+                    size.width = SizeMode::Manual(100.0);
+                    size.height = SizeMode::Auto;
+                    size.aspect_ratio = Some(2.0);
+                }
+                2 => {
+                    size.width = SizeMode::Auto;
+                    size.height = SizeMode::Manual(40.0);
+                    size.max_width = SizeMode::Manual(50.0);
+                    size.aspect_ratio = Some(1.5);
+                }
+                _ => {}
+            }
+            Some(child)
+        },
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let derived_height = get_area(&layers, 1);
+    let clamped_width = get_area(&layers, 2);
+
+    assert_eq!((derived_height.width, derived_height.height), (100.0, 50.0));
+    assert_eq!(
+        (clamped_width.y, clamped_width.width, clamped_width.height),
+        (50.0, 50.0, 40.0)
+    );
+}
+
+#[test]
+fn relative_units() {
+    let mut node = TEST_NODE.clone();
+    node.state = node.state.set_size(Size {
+        padding: (10.0, 10.0, 10.0, 10.0),
+        ..expanded_size()
+    });
+    node.state.font_style.font_size = 10.0;
+    node.node_type = NodeType::Element {
+        tag: "rect".to_string(),
+        namespace: None,
+        children: vec![ElementId(1)],
+    };
+    let mut layers = Layers::default();
+    measure_node_layout(
+        &NodeData { node },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        NodeArea {
+            x: 0.0,
+            y: 0.0,
+            height: 300.0,
+            width: 200.0,
+        },
+        &mut (),
+        &mut layers,
+        |id, _| {
+            let mut child = square_child(id.0)?;
+            child.node.state.font_style.font_size = 5.0;
+            let size = &mut child.node.state.size;
+            size.width = SizeMode::Calculation(CalcType::ViewportWidth(50.0));
+            size.height = SizeMode::Calculation(CalcType::Add(
+                Box::new(CalcType::Em(2.0)),
+                Box::new(CalcType::Rem(3.0)),
+            ));
+            Some(child)
+        },
+        0,
+        &mut FontCollection::new(),
+        &Arc::new(Mutex::new(LayoutMemorizer::new())),
+        true,
+    );
+
+    let child = get_area(&layers, 1);
+
+    assert_eq!((child.width, child.height), (100.0, 40.0));
+}
+
 fn expanded_size() -> Size {
     Size {
         width: SizeMode::Percentage(100.0),
@@ -219,7 +995,54 @@ fn expanded_size() -> Size {
         max_height: SizeMode::Auto,
         max_width: SizeMode::Auto,
         padding: (0.0, 0.0, 0.0, 0.0),
+        margin: (0.0, 0.0, 0.0, 0.0),
         direction: DirectionMode::Both,
+        wrap: WrapMode::NoWrap,
+        main_align: AlignmentMode::Start,
+        cross_align: AlignmentMode::Start,
+        row_gap: 0.0,
+        column_gap: 0.0,
+        position: PositionMode::Static,
+        top: SizeMode::Auto,
+        right: SizeMode::Auto,
+        bottom: SizeMode::Auto,
+        left: SizeMode::Auto,
+        grid_columns: Vec::new(),
+        grid_rows: Vec::new(),
+        grid_column: None,
+        grid_row: None,
+        grid_column_span: 1,
+        grid_row_span: 1,
+        aspect_ratio: None,
         id: 0,
     }
 }
+
+fn square_child(id: usize) -> Option<NodeData> {
+    Some(NodeData {
+        node: Node {
+            id: ElementId(id),
+            parent: None,
+            state: NodeState::default().set_size(Size {
+                width: SizeMode::Manual(50.0),
+                height: SizeMode::Manual(50.0),
+                ..expanded_size()
+            }),
+            node_type: NodeType::Element {
+                tag: "rect".to_string(),
+                namespace: None,
+                children: Vec::new(),
+            },
+            height: 1,
+        },
+    })
+}
+
+fn get_area(layers: &Layers, id: usize) -> NodeArea {
+    layers
+        .layers
+        .values()
+        .find_map(|layer| layer.get(&ElementId(id)))
+        .map(|render_data| render_data.node_area)
+        .unwrap()
+}
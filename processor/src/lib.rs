@@ -11,9 +11,9 @@ use freya_elements::{
 };
 use freya_layers::{Layers, NodeData, RenderData};
 use freya_layout::measure_node_layout;
-use freya_node_state::NodeState;
-use rustc_hash::FxHashMap;
-use skia_safe::{textlayout::FontCollection, Color};
+use freya_node_state::{NodeState, Transform};
+use rustc_hash::{FxHashMap, FxHashSet};
+use skia_safe::{textlayout::FontCollection, Matrix, Rect};
 use std::{
     ops::Index,
     sync::{Arc, Mutex},
@@ -29,11 +29,23 @@ pub type SafeEventEmitter = Arc<Mutex<Option<UnboundedSender<SchedulerMsg>>>>;
 pub type SafeLayoutManager = Arc<Mutex<LayoutMemorizer>>;
 pub type SafeFreyaEvents = Arc<Mutex<Vec<FreyaEvent>>>;
 pub type ViewportsCollection = FxHashMap<ElementId, (Option<NodeArea>, Vec<ElementId>)>;
+/// Transformation matrices of the elements that are transformed by themselves or by some ancestor
+pub type TransformsCollection = FxHashMap<ElementId, Matrix>;
+
+/// What the render has to do, in order
+pub enum RenderStep<'a> {
+    /// Start compositing a group, everything rendered until it's closed is composited as a whole
+    OpenGroup(&'a RenderData),
+    /// Render an element
+    Element(&'a RenderData),
+    /// Composite the last opened group
+    CloseGroup(&'a RenderData),
+}
 
 /// The Work Loop has a few jobs:
 /// - Measure the nodes layouts
 /// - Organize the nodes layouts in layers
-/// - Calculate all the nodes viewports
+/// - Calculate all the nodes viewports and transforms
 /// - Call the render to paint
 /// - Calculate what events must be triggered
 #[allow(clippy::too_many_arguments)]
@@ -46,11 +58,12 @@ pub fn process_work<HookOptions>(
     events_processor: &mut EventsProcessor,
     manager: &SafeLayoutManager,
     hook_options: &mut HookOptions,
-    render_hook: impl Fn(
+    mut render_hook: impl FnMut(
         &SafeDOM,
-        &RenderData,
+        &RenderStep,
         &mut FontCollection,
         &ViewportsCollection,
+        &TransformsCollection,
         &mut HookOptions,
     ),
 ) {
@@ -126,33 +139,41 @@ pub fn process_work<HookOptions>(
         }
     }
 
+    let transforms_collection = calculate_transforms(layers);
+
     // Render all the layers from the bottom to the top
-    for layer_num in &layers_nums {
-        let layer = layers.layers.get(layer_num).unwrap();
-        'elements: for element in layer.values() {
+    'steps: for step in render_steps(layers, &layers_nums) {
+        if let RenderStep::Element(element) = step {
             let viewports = viewports_collection.get(&element.node_id);
+            let element_area = transformed_area(
+                element.node_area,
+                transforms_collection.get(&element.node_id),
+            );
 
             // Skip elements that are totally out of some their parent's viewport
             if let Some((_, viewports)) = viewports {
                 for viewport_id in viewports {
                     let viewport = viewports_collection.get(viewport_id).unwrap().0;
                     if let Some(viewport) = viewport {
-                        if viewport.is_area_outside(element.node_area) {
-                            continue 'elements;
+                        let viewport =
+                            transformed_area(viewport, transforms_collection.get(viewport_id));
+                        if viewport.is_area_outside(element_area) {
+                            continue 'steps;
                         }
                     }
                 }
             }
-
-            // Let the render know what to actually render
-            render_hook(
-                dom,
-                element,
-                font_collection,
-                &viewports_collection,
-                hook_options,
-            )
         }
+
+        // Let the render know what to actually render
+        render_hook(
+            dom,
+            &step,
+            font_collection,
+            &viewports_collection,
+            &transforms_collection,
+            hook_options,
+        )
     }
 
     // Calculated events are those that match considering their viewports
@@ -183,8 +204,12 @@ pub fn process_work<HookOptions>(
                     if let Some((name, cursor)) = data {
                         let ((x, y), (x2, y2)) = area.get_rect();
 
-                        let cursor_is_inside =
-                            cursor.0 > x && cursor.0 < x2 && cursor.1 > y && cursor.1 < y2;
+                        // Transformed elements are hit where they are drawn
+                        let element_cursor =
+                            untransform_point(transforms_collection.get(&element.node_id), *cursor);
+                        let cursor_is_inside = element_cursor.map_or(false, |cursor| {
+                            cursor.0 > x && cursor.0 < x2 && cursor.1 > y && cursor.1 < y2
+                        });
 
                         // Make sure the cursor is inside the node area
                         if cursor_is_inside {
@@ -195,7 +220,13 @@ pub fn process_work<HookOptions>(
                                 for viewport_id in viewports {
                                     let viewport = viewports_collection.get(viewport_id).unwrap().0;
                                     if let Some(viewport) = viewport {
-                                        if viewport.is_point_outside(*cursor) {
+                                        let viewport_cursor = untransform_point(
+                                            transforms_collection.get(viewport_id),
+                                            *cursor,
+                                        );
+                                        if viewport_cursor.map_or(true, |cursor| {
+                                            viewport.is_point_outside(cursor)
+                                        }) {
                                             continue 'events;
                                         }
                                     }
@@ -227,14 +258,12 @@ pub fn process_work<HookOptions>(
         'event_nodes: for (node, request) in event_nodes.iter() {
             for listener in &listeners {
                 if listener.id == node.node_id {
-                    if node.node_state.style.background != Color::TRANSPARENT
-                        && event_name == &"wheel"
+                    if !node.node_state.style.background.is_transparent() && event_name == &"wheel"
                     {
                         break 'event_nodes;
                     }
 
-                    if node.node_state.style.background != Color::TRANSPARENT
-                        && event_name == &"click"
+                    if !node.node_state.style.background.is_transparent() && event_name == &"click"
                     {
                         found_nodes.clear();
                     }
@@ -255,21 +284,26 @@ pub fn process_work<HookOptions>(
 
         for (node, request) in found_nodes {
             let event = match request {
-                FreyaEvent::Mouse { cursor, button, .. } => Some(UserEvent {
-                    scope_id: None,
-                    priority: EventPriority::Medium,
-                    element: Some(node.node_id),
-                    name: event_name,
-                    bubbles: false,
-                    data: Arc::new(MouseData::new(
-                        Point2D::from_lengths(Length::new(cursor.0), Length::new(cursor.1)),
-                        Point2D::from_lengths(
-                            Length::new(cursor.0 - node.node_area.x as f64),
-                            Length::new(cursor.1 - node.node_area.y as f64),
-                        ),
-                        *button,
-                    )),
-                }),
+                FreyaEvent::Mouse { cursor, button, .. } => {
+                    let element_cursor =
+                        untransform_point(transforms_collection.get(&node.node_id), *cursor)
+                            .unwrap_or(*cursor);
+                    Some(UserEvent {
+                        scope_id: None,
+                        priority: EventPriority::Medium,
+                        element: Some(node.node_id),
+                        name: event_name,
+                        bubbles: false,
+                        data: Arc::new(MouseData::new(
+                            Point2D::from_lengths(Length::new(cursor.0), Length::new(cursor.1)),
+                            Point2D::from_lengths(
+                                Length::new(element_cursor.0 - node.node_area.x as f64),
+                                Length::new(element_cursor.1 - node.node_area.y as f64),
+                            ),
+                            *button,
+                        )),
+                    })
+                }
                 FreyaEvent::Wheel { scroll, .. } => Some(UserEvent {
                     scope_id: None,
                     priority: EventPriority::Medium,
@@ -317,3 +351,166 @@ pub fn process_work<HookOptions>(
 
     freya_events.lock().unwrap().clear();
 }
+
+/// Order in which the elements are rendered. Same as the layers, except that the descendants
+/// of a group are rendered right after it so they are composited together, like a stacking context.
+/// The descendants of a group are still ordered by their layers, so a `layer` only moves an
+/// element above or below the other elements of its group.
+fn render_steps<'a>(layers: &'a Layers, layers_nums: &[&i16]) -> Vec<RenderStep<'a>> {
+    let elements = layers_nums
+        .iter()
+        .flat_map(|layer_num| layers.layers.get(layer_num).unwrap().values())
+        .collect::<Vec<&RenderData>>();
+
+    let mut parents = FxHashMap::<ElementId, ElementId>::default();
+    for element in &elements {
+        if let NodeType::Element { children, .. } = &element.node_type {
+            parents.extend(children.iter().map(|child_id| (*child_id, element.node_id)));
+        }
+    }
+
+    let groups = elements
+        .iter()
+        .filter(|element| element.is_group())
+        .map(|element| element.node_id)
+        .collect::<FxHashSet<ElementId>>();
+
+    // Elements of every group, those that don't belong to any are in the `None` group
+    let mut groups_elements = FxHashMap::<Option<ElementId>, Vec<&RenderData>>::default();
+    for element in elements {
+        let mut group = parents.get(&element.node_id);
+        while let Some(parent_id) = group {
+            if groups.contains(parent_id) {
+                break;
+            }
+            group = parents.get(parent_id);
+        }
+        groups_elements
+            .entry(group.copied())
+            .or_default()
+            .push(element);
+    }
+
+    let mut steps = Vec::new();
+    push_group_steps(None, &groups_elements, &mut steps);
+    steps
+}
+
+fn push_group_steps<'a>(
+    group: Option<ElementId>,
+    groups_elements: &FxHashMap<Option<ElementId>, Vec<&'a RenderData>>,
+    steps: &mut Vec<RenderStep<'a>>,
+) {
+    for element in groups_elements.get(&group).into_iter().flatten() {
+        if element.is_group() {
+            // Fully transparent groups are not rendered at all
+            if element.node_state.style.opacity == Some(0.0) {
+                continue;
+            }
+            steps.push(RenderStep::OpenGroup(element));
+            steps.push(RenderStep::Element(element));
+            push_group_steps(Some(element.node_id), groups_elements, steps);
+            steps.push(RenderStep::CloseGroup(element));
+        } else {
+            steps.push(RenderStep::Element(element));
+        }
+    }
+}
+
+/// Combine the transforms of the elements with the ones of their ancestors,
+/// elements that are not transformed at all are left out
+fn calculate_transforms(layers: &Layers) -> TransformsCollection {
+    let elements = layers
+        .layers
+        .values()
+        .flat_map(|layer| layer.values())
+        .map(|element| (element.node_id, element))
+        .collect::<FxHashMap<ElementId, &RenderData>>();
+
+    let mut transforms_collection = TransformsCollection::default();
+
+    // From the root to the leafs
+    let mut pending: Vec<(ElementId, Option<Matrix>)> = vec![(ElementId(0), None)];
+    while let Some((element_id, inherited_transform)) = pending.pop() {
+        let element = match elements.get(&element_id) {
+            Some(element) => element,
+            None => continue,
+        };
+
+        let transform = match (inherited_transform, local_transform(element)) {
+            (Some(inherited_transform), Some(local_transform)) => {
+                Some(Matrix::concat(&inherited_transform, &local_transform))
+            }
+            (inherited_transform, local_transform) => inherited_transform.or(local_transform),
+        };
+
+        if let NodeType::Element { children, .. } = &element.node_type {
+            pending.extend(children.iter().map(|child_id| (*child_id, transform)));
+        }
+
+        if let Some(transform) = transform {
+            transforms_collection.insert(element_id, transform);
+        }
+    }
+
+    transforms_collection
+}
+
+/// Transformation matrix of an element, relative to its transform origin
+fn local_transform(element: &RenderData) -> Option<Matrix> {
+    let style = &element.node_state.style;
+    if style.transform.is_empty() {
+        return None;
+    }
+
+    let area = &element.node_area;
+    let origin_x = area.x + style.transform_origin.x.resolve(area.width);
+    let origin_y = area.y + style.transform_origin.y.resolve(area.height);
+
+    let mut matrix = Matrix::translate((origin_x, origin_y));
+    for transform in &style.transform {
+        match transform {
+            Transform::Translate(x, y) => matrix.pre_translate((*x, *y)),
+            Transform::Scale(x, y) => matrix.pre_scale((*x, *y), None),
+            Transform::Rotate(angle) => matrix.pre_rotate(*angle, None),
+        };
+    }
+    matrix.pre_translate((-origin_x, -origin_y));
+
+    Some(matrix)
+}
+
+/// Bounding box of an area once it's transformed
+fn transformed_area(area: NodeArea, transform: Option<&Matrix>) -> NodeArea {
+    match transform {
+        Some(transform) => {
+            let (rect, _) = transform.map_rect(Rect::new(
+                area.x,
+                area.y,
+                area.x + area.width,
+                area.y + area.height,
+            ));
+            NodeArea {
+                x: rect.left,
+                y: rect.top,
+                width: rect.width(),
+                height: rect.height(),
+            }
+        }
+        None => area,
+    }
+}
+
+/// Map a point of the window to the untransformed space of an element,
+/// `None` if the element is transformed in a way that can't be reverted, e.g scaled to 0
+fn untransform_point(transform: Option<&Matrix>, point: (f64, f64)) -> Option<(f64, f64)> {
+    match transform {
+        Some(transform) => {
+            let point = transform
+                .invert()?
+                .map_point((point.0 as f32, point.1 as f32));
+            Some((point.x as f64, point.y as f64))
+        }
+        None => Some(point),
+    }
+}
@@ -0,0 +1,145 @@
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use dioxus_core::ElementId;
+use dioxus_native_core::real_dom::RealDom;
+use freya_node_state::NodeState;
+use skia_safe::{image::CachingHint, svg, Color, Data, Image};
+
+/// State of an image loaded from a file
+#[derive(Clone)]
+pub enum ImageLoad {
+    Loading,
+    Loaded(Image),
+    /// Why the file couldn't be read or decoded
+    Failed(String),
+}
+
+/// Decoded images, keyed by the hash of their encoded content so the same image is only decoded once,
+/// images loaded from files, and parsed SVGs of every node.
+/// They are kept as long as some node that requested them still exists, even if it's not drawn.
+#[derive(Default)]
+pub struct ImagesCache {
+    /// `None` for the images that couldn't be decoded
+    images: HashMap<u64, Option<Image>>,
+    /// Image last requested by every node
+    images_nodes: HashMap<ElementId, u64>,
+    /// Images of every path, updated from the background threads that load them
+    files: Arc<Mutex<HashMap<String, ImageLoad>>>,
+    /// File last requested by every node
+    files_nodes: HashMap<ElementId, String>,
+    /// Parsed SVGs along with the hash of the data and color they were parsed with
+    svgs: HashMap<ElementId, (u64, Option<svg::Dom>)>,
+}
+
+impl ImagesCache {
+    /// Get the decoded image of some encoded data, decoding it if it wasn't already.
+    /// `key` is the hash of the data, see [`hash_image_data`](freya_node_state::hash_image_data).
+    pub fn get(&mut self, node_id: ElementId, key: u64, encoded: &[u8]) -> Option<Image> {
+        self.images_nodes.insert(node_id, key);
+        self.images
+            .entry(key)
+            .or_insert_with(|| decode_image(encoded))
+            .clone()
+    }
+
+    /// Get the image of a file, it's read and decoded in a background thread the first time it's requested
+    pub fn get_file(&mut self, node_id: ElementId, path: &str) -> ImageLoad {
+        if self.files_nodes.get(&node_id).map(String::as_str) != Some(path) {
+            self.files_nodes.insert(node_id, path.to_string());
+        }
+
+        let mut files = self.files.lock().unwrap();
+        if let Some(image) = files.get(path) {
+            return image.clone();
+        }
+        files.insert(path.to_string(), ImageLoad::Loading);
+
+        let files = self.files.clone();
+        let path = path.to_string();
+        thread::spawn(move || {
+            let image = match std::fs::read(&path) {
+                Ok(encoded) => decode_image(&encoded).map_or_else(
+                    || ImageLoad::Failed("unsupported image format".to_string()),
+                    ImageLoad::Loaded,
+                ),
+                Err(err) => ImageLoad::Failed(err.to_string()),
+            };
+            files.lock().unwrap().insert(path, image);
+        });
+
+        ImageLoad::Loading
+    }
+
+    /// Get the parsed SVG of a node, parsing it again if its data or color changed.
+    /// The `currentColor` keyword of the SVG is replaced with the given color.
+    pub fn get_svg(
+        &mut self,
+        node_id: ElementId,
+        svg_data: &[u8],
+        color: Color,
+    ) -> Option<&mut svg::Dom> {
+        let key = hash(&(svg_data, color.r(), color.g(), color.b()));
+
+        let (cached_key, svg_dom) = self
+            .svgs
+            .entry(node_id)
+            .or_insert_with(|| (key, parse_svg(svg_data, color)));
+
+        if *cached_key != key {
+            *cached_key = key;
+            *svg_dom = parse_svg(svg_data, color);
+        }
+
+        svg_dom.as_mut()
+    }
+
+    /// Forget the images and SVGs that no existing node requested
+    pub fn collect_unused(&mut self, rdom: &RealDom<NodeState>) {
+        let exists = |node_id: &ElementId| rdom.get(*node_id).is_some();
+
+        self.images_nodes.retain(|node_id, _| exists(node_id));
+        let used = self.images_nodes.values().collect::<HashSet<&u64>>();
+        self.images.retain(|key, _| used.contains(key));
+
+        self.files_nodes.retain(|node_id, _| exists(node_id));
+        let used_files = self.files_nodes.values().collect::<HashSet<&String>>();
+        self.files
+            .lock()
+            .unwrap()
+            .retain(|path, _| used_files.contains(path));
+
+        self.svgs.retain(|node_id, _| exists(node_id));
+    }
+}
+
+/// Decode an image right away rather than when it's first drawn
+fn decode_image(encoded: &[u8]) -> Option<Image> {
+    Image::from_encoded(Data::new_copy(encoded))
+        .and_then(|image| image.to_raster_image(CachingHint::Allow))
+}
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn parse_svg(svg_data: &[u8], color: Color) -> Option<svg::Dom> {
+    let svg_data = match std::str::from_utf8(svg_data) {
+        // The alpha of the color is left out, SVG colors are opaque
+        Ok(svg) if svg.contains("currentColor") => Cow::Owned(
+            svg.replace(
+                "currentColor",
+                &format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b()),
+            )
+            .into_bytes(),
+        ),
+        _ => Cow::Borrowed(svg_data),
+    };
+    svg::Dom::from_bytes(&svg_data).ok()
+}
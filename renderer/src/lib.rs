@@ -1,20 +1,30 @@
+use freya_node_state::SafeDiagnostics;
 use freya_processor::events::FreyaEvent;
 use freya_processor::{SafeDOM, SafeEventEmitter, SafeLayoutManager};
 use glutin::event::ElementState;
 use glutin::window::WindowId;
-use glutin::{event::Event, event_loop::ControlFlow};
+use glutin::{
+    event::{Event, StartCause},
+    event_loop::ControlFlow,
+};
 use glutin::{
     event::{KeyEvent, MouseScrollDelta, TouchPhase, WindowEvent},
     event_loop::EventLoop,
 };
+pub use images_cache::{ImageLoad, ImagesCache};
+use offscreen::{create_offscreen_envs, redraw_offscreen_envs, FRAME_DURATION};
+pub use offscreen::{create_raster_surface, run_offscreen, OffscreenEnv};
+pub use renderer::{render_skia, render_step};
 use skia_safe::{textlayout::FontCollection, FontMgr};
 use std::{
     sync::{Arc, Mutex},
     time::Instant,
 };
 pub use window::{create_surface, create_windows_from_config, WindowEnv};
-pub use window_config::WindowConfig;
+pub use window_config::{FrameCallback, OffscreenController, RenderBackend, WindowConfig};
 
+mod images_cache;
+mod offscreen;
 mod renderer;
 mod window;
 mod window_config;
@@ -25,14 +35,29 @@ pub fn run<T: 'static + Clone>(
         SafeDOM,
         SafeEventEmitter,
         SafeLayoutManager,
+        SafeDiagnostics,
         WindowConfig<T>,
     )>,
 ) {
+    // Windows using the raster backend don't need an event loop nor a GL context
+    let (offscreen_config, windows_config): (Vec<_>, Vec<_>) = windows_config
+        .into_iter()
+        .partition(|(_, _, _, _, win_config)| win_config.backend == RenderBackend::Raster);
+
+    if windows_config.is_empty() {
+        run_offscreen(offscreen_config);
+        return;
+    }
+
     let cursor_pos = Arc::new(Mutex::new((0.0, 0.0)));
     let event_loop = EventLoop::<WindowId>::with_user_event();
     let mut font_collection = FontCollection::new();
     font_collection.set_default_font_manager(FontMgr::default(), "Fira Sans");
 
+    // Offscreen windows are rendered on a timer of the event loop
+    let mut offscreen_envs = create_offscreen_envs(offscreen_config, font_collection.clone());
+    let mut next_offscreen_frame = Instant::now();
+
     let wins = create_windows_from_config(windows_config, &event_loop, font_collection);
 
     let get_window_env = move |window_id: WindowId| -> Option<Arc<Mutex<WindowEnv<T>>>> {
@@ -47,10 +72,19 @@ pub fn run<T: 'static + Clone>(
     };
 
     event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Wait;
+        *control_flow = if offscreen_envs.is_empty() {
+            ControlFlow::Wait
+        } else {
+            ControlFlow::WaitUntil(next_offscreen_frame)
+        };
 
         match event {
             Event::LoopDestroyed => {}
+            Event::NewEvents(StartCause::ResumeTimeReached { .. }) => {
+                redraw_offscreen_envs(&mut offscreen_envs);
+                next_offscreen_frame = Instant::now() + FRAME_DURATION;
+                *control_flow = ControlFlow::WaitUntil(next_offscreen_frame);
+            }
             Event::WindowEvent {
                 event, window_id, ..
             } => {
@@ -0,0 +1,200 @@
+use freya_common::NodeArea;
+use freya_node_state::SafeDiagnostics;
+use freya_processor::{
+    events::EventsProcessor, process_work, SafeDOM, SafeEventEmitter, SafeFreyaEvents,
+    SafeLayoutManager,
+};
+use skia_safe::{textlayout::FontCollection, Color, FontMgr, Image, Surface};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::images_cache::ImagesCache;
+use crate::renderer::render_step;
+use crate::window_config::WindowConfig;
+
+/// Information related to a window rendered in an offscreen raster surface
+pub struct OffscreenEnv<T: Clone> {
+    pub(crate) surface: Surface,
+    pub(crate) dom: SafeDOM,
+    pub(crate) layout_memorizer: SafeLayoutManager,
+    pub(crate) diagnostics: SafeDiagnostics,
+    pub(crate) freya_events: SafeFreyaEvents,
+    pub(crate) event_emitter: SafeEventEmitter,
+    pub(crate) font_collection: FontCollection,
+    pub(crate) images_cache: ImagesCache,
+    pub(crate) events_processor: EventsProcessor,
+    pub(crate) win_config: WindowConfig<T>,
+}
+
+impl<T: Clone> OffscreenEnv<T> {
+    pub fn new(
+        dom: SafeDOM,
+        event_emitter: SafeEventEmitter,
+        layout_memorizer: SafeLayoutManager,
+        diagnostics: SafeDiagnostics,
+        win_config: WindowConfig<T>,
+        font_collection: FontCollection,
+    ) -> Self {
+        Self {
+            surface: create_raster_surface(win_config.width, win_config.height),
+            dom,
+            layout_memorizer,
+            diagnostics,
+            freya_events: Arc::new(Mutex::new(Vec::new())),
+            event_emitter,
+            font_collection,
+            images_cache: ImagesCache::default(),
+            events_processor: EventsProcessor::default(),
+            win_config,
+        }
+    }
+
+    /// Measure, paint and process the events of a new frame
+    pub fn redraw(&mut self) {
+        let canvas = self.surface.canvas();
+
+        canvas.clear(if self.win_config.transparent {
+            Color::TRANSPARENT
+        } else {
+            Color::WHITE
+        });
+
+        process_work(
+            &self.dom,
+            NodeArea {
+                width: self.win_config.width as f32,
+                height: self.win_config.height as f32,
+                x: 0.0,
+                y: 0.0,
+            },
+            self.freya_events.clone(),
+            &self.event_emitter,
+            &mut self.font_collection,
+            &mut self.events_processor,
+            &self.layout_memorizer,
+            canvas,
+            |dom, step, font_collection, viewports_collection, transforms_collection, canvas| {
+                render_step(
+                    dom,
+                    canvas,
+                    step,
+                    font_collection,
+                    viewports_collection,
+                    transforms_collection,
+                    &mut self.images_cache,
+                    &self.diagnostics,
+                );
+            },
+        );
+
+        self.images_cache.collect_unused(&self.dom.lock().unwrap());
+
+        if let Some(on_frame) = self.win_config.on_frame.clone() {
+            on_frame(&self.snapshot());
+        }
+    }
+
+    /// Get an image of the last rendered frame
+    pub fn snapshot(&mut self) -> Image {
+        self.surface.image_snapshot()
+    }
+
+    /// Resize the raster surface, the next frame will be measured from scratch
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.win_config.width = width;
+        self.win_config.height = height;
+        self.surface = create_raster_surface(width, height);
+        self.layout_memorizer.lock().unwrap().dirty_nodes.clear();
+        self.layout_memorizer.lock().unwrap().nodes.clear();
+    }
+
+    /// Whether the window was closed through its controller
+    pub fn is_closed(&self) -> bool {
+        self.win_config
+            .controller
+            .as_ref()
+            .map_or(false, |controller| controller.is_closed())
+    }
+
+    /// Queue events to be processed in the next frame
+    pub fn freya_events(&self) -> SafeFreyaEvents {
+        self.freya_events.clone()
+    }
+}
+
+/// Create a CPU raster surface of the given size
+pub fn create_raster_surface(width: u32, height: u32) -> Surface {
+    Surface::new_raster_n32_premul((width as i32, height as i32)).unwrap()
+}
+
+/// Duration of a frame of the offscreen windows
+pub(crate) const FRAME_DURATION: Duration = Duration::from_millis(1000 / 75);
+
+/// Create the environments of the given offscreen windows
+pub(crate) fn create_offscreen_envs<T: Clone>(
+    windows_config: Vec<(
+        SafeDOM,
+        SafeEventEmitter,
+        SafeLayoutManager,
+        SafeDiagnostics,
+        WindowConfig<T>,
+    )>,
+    font_collection: FontCollection,
+) -> Vec<OffscreenEnv<T>> {
+    windows_config
+        .into_iter()
+        .map(
+            |(dom, event_emitter, layout_memorizer, diagnostics, win_config)| {
+                OffscreenEnv::new(
+                    dom,
+                    event_emitter,
+                    layout_memorizer,
+                    diagnostics,
+                    win_config,
+                    font_collection.clone(),
+                )
+            },
+        )
+        .collect()
+}
+
+/// Render a frame of every offscreen window, the closed ones are dropped
+pub(crate) fn redraw_offscreen_envs<T: Clone>(envs: &mut Vec<OffscreenEnv<T>>) {
+    envs.retain(|env| !env.is_closed());
+    for env in envs {
+        let size = env
+            .win_config
+            .controller
+            .as_ref()
+            .and_then(|controller| controller.take_size());
+        if let Some((width, height)) = size {
+            env.resize(width, height);
+        }
+        env.redraw();
+    }
+}
+
+/// Run the render loop of the given offscreen windows, this blocks the current thread
+/// until all the windows are closed through their [`OffscreenController`](crate::OffscreenController).
+pub fn run_offscreen<T: Clone>(
+    windows_config: Vec<(
+        SafeDOM,
+        SafeEventEmitter,
+        SafeLayoutManager,
+        SafeDiagnostics,
+        WindowConfig<T>,
+    )>,
+) {
+    let mut font_collection = FontCollection::new();
+    font_collection.set_default_font_manager(FontMgr::default(), "Fira Sans");
+
+    let mut envs = create_offscreen_envs(windows_config, font_collection);
+
+    while !envs.is_empty() {
+        redraw_offscreen_envs(&mut envs);
+        thread::sleep(FRAME_DURATION);
+    }
+}
@@ -1,98 +1,156 @@
 use dioxus_core::ElementId;
 use dioxus_native_core::real_dom::{Node, NodeType};
 use dioxus_native_core::traversable::Traversable;
+use freya_common::NodeArea;
 use freya_layers::RenderData;
-use freya_node_state::NodeState;
-use freya_processor::ViewportsCollection;
+use freya_node_state::{
+    AttributeError, AttributeErrorReason, Border, BorderAlignment, BorderStyle, CornerRadius, Fill,
+    Filters, GradientStop, ImageSampling, NodeState, SafeDiagnostics, ShadowSettings, Style,
+};
+use freya_processor::{RenderStep, TransformsCollection, ViewportsCollection};
 use skia_safe::textlayout::{Paragraph, RectHeightStyle, RectWidthStyle, TextHeightBehavior};
 use skia_safe::Color;
 use skia_safe::{
+    canvas::SaveLayerRec,
+    color_filters, image_filters,
+    paint::Cap,
     svg,
-    textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle, TextStyle},
-    BlurStyle, Canvas, ClipOp, Data, IRect, Image, MaskFilter, Paint, PaintStyle, Path,
-    PathDirection, Rect,
+    textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle},
+    BlurStyle, Canvas, ClipOp, ColorMatrix, CubicResampler, FilterMode, IRect, Image, ImageFilter,
+    MaskFilter, Matrix, MipmapMode, Paint, PaintStyle, Path, PathEffect, PathFillType, Point,
+    RRect, Rect, SamplingOptions, Shader, TileMode, U8CPU,
 };
 
+use crate::images_cache::{ImageLoad, ImagesCache};
 use crate::SafeDOM;
 
+/// Render a step of the work loop, groups are composited in a layer of their own
+#[allow(clippy::too_many_arguments)]
+pub fn render_step(
+    dom: &SafeDOM,
+    canvas: &mut Canvas,
+    step: &RenderStep,
+    font_collection: &mut FontCollection,
+    viewports_collection: &ViewportsCollection,
+    transforms_collection: &TransformsCollection,
+    images_cache: &mut ImagesCache,
+    diagnostics: &SafeDiagnostics,
+) {
+    match step {
+        RenderStep::OpenGroup(node) => {
+            let base_matrix = canvas.local_to_device_as_3x3();
+
+            // Restored when the group is closed
+            canvas.save();
+            clip_viewports(
+                canvas,
+                node,
+                viewports_collection,
+                transforms_collection,
+                &base_matrix,
+            );
+
+            // The backdrop of the group is not faded nor filtered along with it
+            draw_node_backdrop(canvas, node);
+
+            let style = &node.node_state.style;
+            let mut paint = Paint::default();
+            paint.set_alpha((style.opacity.unwrap_or(1.0) * 255.0).round() as U8CPU);
+            // Filters are applied in the space of the group, so e.g the blur is scaled along with it
+            if let Some(image_filter) = filters_image_filter(&style.filters) {
+                paint.set_image_filter(image_filter);
+            }
+            canvas.save_layer(&SaveLayerRec::default().paint(&paint));
+
+            // The elements of the group are rendered as usual
+            canvas.reset_matrix();
+            canvas.concat(&base_matrix);
+        }
+        RenderStep::Element(node) => {
+            let save_count = canvas.save();
+            render_skia(
+                dom,
+                canvas,
+                node,
+                font_collection,
+                viewports_collection,
+                transforms_collection,
+                images_cache,
+                diagnostics,
+            );
+            canvas.restore_to_count(save_count);
+        }
+        RenderStep::CloseGroup(_) => {
+            // Composite the layer and then undo the clipping of the group
+            canvas.restore();
+            canvas.restore();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_skia(
     dom: &SafeDOM,
     canvas: &mut Canvas,
     node: &RenderData,
     font_collection: &mut FontCollection,
     viewports_collection: &ViewportsCollection,
+    transforms_collection: &TransformsCollection,
+    images_cache: &mut ImagesCache,
+    diagnostics: &SafeDiagnostics,
 ) {
     if let NodeType::Element { tag, children, .. } = &node.node_type {
-        let viewports = viewports_collection.get(&node.node_id);
-        if let Some((_, viewports)) = viewports {
-            for viewport_id in viewports {
-                let viewport = viewports_collection.get(viewport_id).unwrap().0;
-                if let Some(viewport) = viewport {
-                    canvas.clip_rect(
-                        Rect::new(
-                            viewport.x,
-                            viewport.y,
-                            viewport.x + viewport.width,
-                            viewport.y + viewport.height,
-                        ),
-                        ClipOp::Intersect,
-                        true,
-                    );
-                }
-            }
+        let base_matrix = canvas.local_to_device_as_3x3();
+
+        clip_viewports(
+            canvas,
+            node,
+            viewports_collection,
+            transforms_collection,
+            &base_matrix,
+        );
+
+        // The backdrop of groups is drawn before opening their layer
+        if !node.is_group() {
+            draw_node_backdrop(canvas, node);
         }
 
         match tag.as_str() {
             "rect" | "container" => {
-                let shadow = &node.node_state.style.shadow;
+                let style = &node.node_state.style;
 
                 #[cfg(not(feature = "wireframe"))]
-                if node.node_state.style.background == Color::TRANSPARENT && shadow.intensity == 0 {
+                if style.background.is_transparent()
+                    && style.shadows.iter().all(|shadow| shadow.intensity == 0)
+                    && !style.border.is_visible()
+                {
                     return;
                 }
 
                 let mut paint = Paint::default();
                 paint.set_anti_alias(true);
                 paint.set_style(PaintStyle::Fill);
-                paint.set_color(node.node_state.style.background);
-
-                let radius = node.node_state.style.radius;
-                let radius = if radius < 0.0 { 0.0 } else { radius };
+                apply_fill(&mut paint, &style.background, &node.node_area);
 
                 let ((x, y), (x2, y2)) = node.node_area.get_rect();
+                let area = Rect::new(x as f32, y as f32, x2 as f32, y2 as f32);
 
-                let mut path = Path::new();
-                path.add_round_rect(
-                    Rect::new(x as f32, y as f32, x2 as f32, y2 as f32),
-                    (radius as f32, radius as f32),
-                    PathDirection::CW,
-                );
-                path.close();
+                let path = rounded_rect_path(area, &style.radius, 0.0);
 
-                // Shadow effect
-                {
-                    if shadow.intensity > 0 {
-                        let mut blur_paint = paint.clone();
-
-                        blur_paint.set_color(shadow.color);
-                        blur_paint.set_alpha(shadow.intensity);
-                        blur_paint.set_mask_filter(MaskFilter::blur(
-                            BlurStyle::Normal,
-                            shadow.size,
-                            false,
-                        ));
-                        canvas.draw_path(&path, &blur_paint);
-                    }
+                for shadow in style.shadows.iter().filter(|shadow| !shadow.inset) {
+                    draw_shadow(canvas, area, &style.radius, shadow);
                 }
 
                 canvas.draw_path(&path, &paint);
+
+                for shadow in style.shadows.iter().filter(|shadow| shadow.inset) {
+                    draw_shadow(canvas, area, &style.radius, shadow);
+                }
+
+                draw_border(canvas, area, &style.radius, &style.border);
             }
             "label" => {
-                let font_size = node.node_state.font_style.font_size;
-                let font_family = &node.node_state.font_style.font_family;
-                let font_color = node.node_state.font_style.color;
                 let align = node.node_state.font_style.align;
-                let font_style = node.node_state.font_style.font_style;
 
                 let mut paint = Paint::default();
 
@@ -123,13 +181,7 @@ pub fn render_skia(
 
                     let mut paragraph_style = ParagraphStyle::default();
                     paragraph_style.set_text_align(align);
-                    paragraph_style.set_text_style(
-                        TextStyle::new()
-                            .set_font_style(font_style)
-                            .set_color(font_color)
-                            .set_font_size(font_size)
-                            .set_font_families(&[font_family]),
-                    );
+                    paragraph_style.set_text_style(&node.node_state.font_style.text_style());
                     let mut paragraph_builder =
                         ParagraphBuilder::new(&paragraph_style, font_collection.clone());
 
@@ -161,13 +213,12 @@ pub fn render_skia(
 
                 for node_text in &texts {
                     paragraph_builder.push_style(
-                        TextStyle::new()
-                            .set_font_style(node_text.0.font_style.font_style)
+                        node_text
+                            .0
+                            .font_style
+                            .text_style()
                             .set_height_override(true)
-                            .set_height(node_text.0.font_style.line_height)
-                            .set_color(node_text.0.font_style.color)
-                            .set_font_size(node_text.0.font_style.font_size)
-                            .set_font_families(&[node_text.0.font_style.font_family.clone()]),
+                            .set_height(node_text.0.font_style.line_height),
                     );
                     paragraph_builder.add_text(node_text.1.clone());
                 }
@@ -190,8 +241,12 @@ pub fn render_skia(
                 let x = node.node_area.x;
                 let y = node.node_area.y;
                 if let Some(svg_data) = &node.node_state.style.svg_data {
-                    let svg_dom = svg::Dom::from_bytes(svg_data);
-                    if let Ok(mut svg_dom) = svg_dom {
+                    let svg_dom = images_cache.get_svg(
+                        node.node_id,
+                        svg_data,
+                        node.node_state.font_style.color,
+                    );
+                    if let Some(svg_dom) = svg_dom {
                         canvas.save();
                         canvas.translate((x, y));
                         svg_dom.set_container_size((
@@ -204,24 +259,47 @@ pub fn render_skia(
                 }
             }
             "image" => {
-                if let Some(image_data) = &node.node_state.style.image_data {
-                    let pic = Image::from_encoded(unsafe { Data::new_bytes(image_data) });
-                    if let Some(pic) = pic {
-                        let mut paint = Paint::default();
-                        paint.set_anti_alias(true);
-                        canvas.draw_image_nine(
-                            pic,
-                            IRect::new(0, 0, 0, 0),
-                            Rect::new(
-                                node.node_area.x,
-                                node.node_area.y,
-                                node.node_area.x + node.node_area.width,
-                                node.node_area.y + node.node_area.height,
+                let style = &node.node_state.style;
+                let image = if let Some(image_data) = &style.image_data {
+                    let image = images_cache.get(node.node_id, style.image_data_hash, image_data);
+                    if image.is_none() {
+                        diagnostics.lock().unwrap().report(AttributeError {
+                            node_id: node.node_id.0,
+                            element: tag.to_string(),
+                            attribute: "image_data".to_string(),
+                            value: format!("{} bytes", image_data.len()),
+                            reason: AttributeErrorReason::LoadFailed(
+                                "unsupported image format".to_string(),
                             ),
-                            skia_safe::FilterMode::Last,
-                            Some(&paint),
-                        );
+                            state: "style",
+                        });
                     }
+                    image
+                } else if let Some(image_path) = &style.image_path {
+                    match images_cache.get_file(node.node_id, image_path) {
+                        ImageLoad::Loaded(image) => Some(image),
+                        ImageLoad::Loading => {
+                            draw_image_placeholder(canvas, &node.node_area);
+                            None
+                        }
+                        ImageLoad::Failed(reason) => {
+                            diagnostics.lock().unwrap().report(AttributeError {
+                                node_id: node.node_id.0,
+                                element: tag.to_string(),
+                                attribute: "image_path".to_string(),
+                                value: image_path.to_string(),
+                                reason: AttributeErrorReason::LoadFailed(reason),
+                                state: "style",
+                            });
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                if let Some(image) = image {
+                    draw_image(canvas, image, &node.node_area, style);
                 }
             }
             _ => {}
@@ -322,3 +400,336 @@ fn draw_cursor(node: &RenderData, paragraph: Paragraph, canvas: &mut Canvas) ->
 
     Some(())
 }
+
+/// Rectangle with rounded corners, grown by `outset` (or shrunk if negative)
+fn rounded_rect(area: Rect, radius: &CornerRadius, outset: f32) -> RRect {
+    // Square corners stay square, rounded ones follow the outset
+    let corner = |radius: f32| {
+        let radius = if radius > 0.0 {
+            (radius + outset).max(0.0)
+        } else {
+            0.0
+        };
+        Point::new(radius, radius)
+    };
+    RRect::new_rect_radii(
+        area.with_outset((outset, outset)),
+        &[
+            corner(radius.top_left),
+            corner(radius.top_right),
+            corner(radius.bottom_right),
+            corner(radius.bottom_left),
+        ],
+    )
+}
+
+fn rounded_rect_path(area: Rect, radius: &CornerRadius, outset: f32) -> Path {
+    let mut path = Path::new();
+    path.add_rrect(rounded_rect(area, radius, outset), None);
+    path
+}
+
+fn draw_shadow(canvas: &mut Canvas, area: Rect, radius: &CornerRadius, shadow: &ShadowSettings) {
+    if shadow.intensity == 0 {
+        return;
+    }
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Fill);
+    paint.set_color(shadow.color);
+    paint.set_alpha(shadow.intensity);
+    if shadow.blur > 0.0 {
+        paint.set_mask_filter(MaskFilter::blur(BlurStyle::Normal, shadow.blur, false));
+    }
+
+    let shadow_area = area.with_offset((shadow.x, shadow.y));
+
+    if shadow.inset {
+        // Paint around a hole with the shape of the node, but only inside of the node
+        let outset = shadow.blur * 3.0 + shadow.x.abs() + shadow.y.abs() + shadow.spread.abs();
+        let mut path = Path::new();
+        path.add_rect(area.with_outset((outset, outset)), None);
+        path.add_rrect(rounded_rect(shadow_area, radius, -shadow.spread), None);
+        path.set_fill_type(PathFillType::EvenOdd);
+
+        canvas.save();
+        canvas.clip_rrect(rounded_rect(area, radius, 0.0), ClipOp::Intersect, true);
+        canvas.draw_path(&path, &paint);
+        canvas.restore();
+    } else {
+        canvas.draw_rrect(rounded_rect(shadow_area, radius, shadow.spread), &paint);
+    }
+}
+
+fn draw_border(canvas: &mut Canvas, area: Rect, radius: &CornerRadius, border: &Border) {
+    if !border.is_visible() {
+        return;
+    }
+
+    // The stroke is centered over the path, so move it to lay inside or outside of the edges
+    let outset = match border.alignment {
+        BorderAlignment::Inner => -border.width / 2.0,
+        BorderAlignment::Outer => border.width / 2.0,
+        BorderAlignment::Center => 0.0,
+    };
+    let path = rounded_rect_path(area, radius, outset);
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(border.width);
+    paint.set_color(border.color);
+
+    match border.style {
+        BorderStyle::Dashed => {
+            paint.set_path_effect(PathEffect::dash(
+                &[border.width * 3.0, border.width * 2.0],
+                0.0,
+            ));
+        }
+        BorderStyle::Dotted => {
+            paint.set_stroke_cap(Cap::Round);
+            paint.set_path_effect(PathEffect::dash(&[0.0, border.width * 2.0], 0.0));
+        }
+        BorderStyle::Solid | BorderStyle::None => {}
+    }
+
+    canvas.draw_path(&path, &paint);
+}
+
+/// Clip the viewports of a node and leave the canvas transformed like the node
+fn clip_viewports(
+    canvas: &mut Canvas,
+    node: &RenderData,
+    viewports_collection: &ViewportsCollection,
+    transforms_collection: &TransformsCollection,
+    base_matrix: &Matrix,
+) {
+    let set_transform = |canvas: &mut Canvas, element_id: &ElementId| {
+        canvas.reset_matrix();
+        canvas.concat(base_matrix);
+        if let Some(transform) = transforms_collection.get(element_id) {
+            canvas.concat(transform);
+        }
+    };
+
+    let viewports = viewports_collection.get(&node.node_id);
+    if let Some((_, viewports)) = viewports {
+        for viewport_id in viewports {
+            let viewport = viewports_collection.get(viewport_id).unwrap().0;
+            if let Some(viewport) = viewport {
+                // Viewports are clipped where they are drawn, with their own transforms
+                set_transform(canvas, viewport_id);
+                canvas.clip_rect(
+                    Rect::new(
+                        viewport.x,
+                        viewport.y,
+                        viewport.x + viewport.width,
+                        viewport.y + viewport.height,
+                    ),
+                    ClipOp::Intersect,
+                    true,
+                );
+            }
+        }
+    }
+
+    set_transform(canvas, &node.node_id);
+}
+
+fn draw_node_backdrop(canvas: &mut Canvas, node: &RenderData) {
+    if let NodeType::Element { tag, .. } = &node.node_type {
+        let style = &node.node_state.style;
+        if (tag == "rect" || tag == "container") && style.backdrop_blur > 0.0 {
+            let area = Rect::from_xywh(
+                node.node_area.x,
+                node.node_area.y,
+                node.node_area.width,
+                node.node_area.height,
+            );
+            draw_backdrop_blur(canvas, area, &style.radius, style.backdrop_blur);
+        }
+    }
+}
+
+/// Blur what has been drawn under an area
+fn draw_backdrop_blur(canvas: &mut Canvas, area: Rect, radius: &CornerRadius, blur: f32) {
+    let backdrop = image_filters::blur((blur, blur), TileMode::Clamp, None, None);
+    if let Some(backdrop) = backdrop {
+        // Blur what has been drawn so far, but only under the node
+        let save_count = canvas.save();
+        canvas.clip_rrect(rounded_rect(area, radius, 0.0), ClipOp::Intersect, true);
+        canvas.save_layer(&SaveLayerRec::default().backdrop(&backdrop));
+        canvas.restore_to_count(save_count);
+    }
+}
+
+fn filters_image_filter(filters: &Filters) -> Option<ImageFilter> {
+    let mut image_filter = None;
+
+    if filters.has_color_filter() {
+        // Grayscale is just the lack of saturation, so both go in the same matrix
+        let s = filters.saturate * (1.0 - filters.grayscale);
+        let b = filters.brightness;
+        #[rustfmt::skip]
+        let matrix = ColorMatrix::new(
+            b * (0.213 + 0.787 * s), b * (0.715 - 0.715 * s), b * (0.072 - 0.072 * s), 0.0, 0.0,
+            b * (0.213 - 0.213 * s), b * (0.715 + 0.285 * s), b * (0.072 - 0.072 * s), 0.0, 0.0,
+            b * (0.213 - 0.213 * s), b * (0.715 - 0.715 * s), b * (0.072 + 0.928 * s), 0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        );
+        image_filter =
+            image_filters::color_filter(color_filters::matrix(&matrix, None), None, None);
+    }
+
+    if filters.blur > 0.0 {
+        image_filter = image_filters::blur(
+            (filters.blur, filters.blur),
+            TileMode::Decal,
+            image_filter,
+            None,
+        );
+    }
+
+    image_filter
+}
+
+fn draw_image_placeholder(canvas: &mut Canvas, area: &NodeArea) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Fill);
+    paint.set_color(Color::from_rgb(235, 235, 235));
+
+    canvas.draw_rect(
+        Rect::from_xywh(area.x, area.y, area.width, area.height),
+        &paint,
+    );
+}
+
+fn draw_image(canvas: &mut Canvas, image: Image, area: &NodeArea, style: &Style) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+
+    if let Some(image_slice) = &style.image_slice {
+        // The center must be left with some pixels, otherwise the image is drawn as usual
+        let center = IRect::new(
+            image_slice.left as i32,
+            image_slice.top as i32,
+            image.width() - image_slice.right as i32,
+            image.height() - image_slice.bottom as i32,
+        );
+        if !center.is_empty() {
+            let filter_mode = match style.sampling {
+                ImageSampling::Nearest => FilterMode::Nearest,
+                _ => FilterMode::Linear,
+            };
+            canvas.draw_image_nine(
+                image,
+                center,
+                Rect::from_xywh(area.x, area.y, area.width, area.height),
+                filter_mode,
+                Some(&paint),
+            );
+            return;
+        }
+    }
+
+    let (width, height) = style.object_fit.fit(
+        (image.width() as f32, image.height() as f32),
+        (area.width, area.height),
+    );
+    let x = area.x + style.object_position.x.resolve(area.width - width);
+    let y = area.y + style.object_position.y.resolve(area.height - height);
+
+    let sampling = match style.sampling {
+        ImageSampling::Nearest => SamplingOptions::new(FilterMode::Nearest, MipmapMode::None),
+        ImageSampling::Linear => SamplingOptions::new(FilterMode::Linear, MipmapMode::None),
+        ImageSampling::Mipmap => SamplingOptions::new(FilterMode::Linear, MipmapMode::Linear),
+        ImageSampling::Cubic => SamplingOptions::from(CubicResampler::mitchell()),
+    };
+
+    // Covering or unscaled images might overflow the node
+    canvas.save();
+    canvas.clip_rect(
+        Rect::from_xywh(area.x, area.y, area.width, area.height),
+        ClipOp::Intersect,
+        true,
+    );
+    canvas.draw_image_rect_with_sampling_options(
+        image,
+        None,
+        Rect::from_xywh(x, y, width, height),
+        sampling,
+        &paint,
+    );
+    canvas.restore();
+}
+
+/// Paint a fill, gradients are stretched over the area of the node
+fn apply_fill(paint: &mut Paint, fill: &Fill, area: &NodeArea) {
+    let center = Point::new(area.x + area.width / 2.0, area.y + area.height / 2.0);
+    let (stops, shader) = match fill {
+        Fill::Color(color) => {
+            paint.set_color(*color);
+            return;
+        }
+        Fill::LinearGradient(gradient) => {
+            // The gradient line goes through the center, long enough to reach the corners
+            let (sin, cos) = gradient.angle.to_radians().sin_cos();
+            let length = (area.width * sin).abs() + (area.height * cos).abs();
+            let offset = Point::new(sin, -cos) * (length / 2.0);
+            let (colors, offsets) = gradient_stops(&gradient.stops);
+            let shader = Shader::linear_gradient(
+                (center - offset, center + offset),
+                &colors[..],
+                &offsets[..],
+                TileMode::Clamp,
+                None,
+                None,
+            );
+            (&gradient.stops, shader)
+        }
+        Fill::RadialGradient(gradient) => {
+            let radius = (area.width / 2.0).hypot(area.height / 2.0);
+            let (colors, offsets) = gradient_stops(&gradient.stops);
+            let shader = Shader::radial_gradient(
+                center,
+                radius,
+                &colors[..],
+                &offsets[..],
+                TileMode::Clamp,
+                None,
+                None,
+            );
+            (&gradient.stops, shader)
+        }
+        Fill::ConicGradient(gradient) => {
+            // Skia sweeps start at the right, conic gradients start at the top
+            let matrix = Matrix::rotate_deg_pivot(gradient.angle - 90.0, center);
+            let (colors, offsets) = gradient_stops(&gradient.stops);
+            let shader = Shader::sweep_gradient(
+                center,
+                &colors[..],
+                &offsets[..],
+                TileMode::Clamp,
+                None,
+                None,
+                &matrix,
+            );
+            (&gradient.stops, shader)
+        }
+    };
+
+    if let Some(shader) = shader {
+        paint.set_color(Color::BLACK);
+        paint.set_shader(shader);
+    } else if let Some(stop) = stops.last() {
+        // Skia can't build a shader for an empty area
+        paint.set_color(stop.color);
+    }
+}
+
+fn gradient_stops(stops: &[GradientStop]) -> (Vec<Color>, Vec<f32>) {
+    stops.iter().map(|stop| (stop.color, stop.offset)).unzip()
+}
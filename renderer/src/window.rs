@@ -1,4 +1,5 @@
 use freya_common::NodeArea;
+use freya_node_state::SafeDiagnostics;
 use freya_processor::{
     events::EventsProcessor, process_work, SafeDOM, SafeEventEmitter, SafeFreyaEvents,
     SafeLayoutManager,
@@ -20,7 +21,8 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::renderer::render_skia;
+use crate::images_cache::ImagesCache;
+use crate::renderer::render_step;
 use crate::window_config::WindowConfig;
 
 type WindowedContext = glutin::ContextWrapper<glutin::PossiblyCurrent, glutin::window::Window>;
@@ -34,9 +36,11 @@ pub struct WindowEnv<T: Clone> {
     pub(crate) fb_info: FramebufferInfo,
     pub(crate) dom: SafeDOM,
     pub(crate) layout_memorizer: SafeLayoutManager,
+    pub(crate) diagnostics: SafeDiagnostics,
     pub(crate) freya_events: SafeFreyaEvents,
     pub(crate) event_emitter: SafeEventEmitter,
     pub(crate) font_collection: FontCollection,
+    pub(crate) images_cache: ImagesCache,
     pub(crate) events_processor: EventsProcessor,
     pub(crate) win_config: WindowConfig<T>,
     pub(crate) is_resizing: Arc<Mutex<bool>>,
@@ -69,13 +73,22 @@ impl<T: Clone> WindowEnv<T> {
             &mut self.events_processor,
             &self.layout_memorizer,
             canvas,
-            |dom, element, font_collection, viewports_collection, canvas| {
-                canvas.save();
-                render_skia(dom, canvas, element, font_collection, viewports_collection);
-                canvas.restore();
+            |dom, step, font_collection, viewports_collection, transforms_collection, canvas| {
+                render_step(
+                    dom,
+                    canvas,
+                    step,
+                    font_collection,
+                    viewports_collection,
+                    transforms_collection,
+                    &mut self.images_cache,
+                    &self.diagnostics,
+                );
             },
         );
 
+        self.images_cache.collect_unused(&self.dom.lock().unwrap());
+
         self.gr_context.flush(None);
         self.windowed_context.swap_buffers().unwrap();
     }
@@ -113,6 +126,7 @@ pub fn create_windows_from_config<T: Clone>(
         SafeDOM,
         SafeEventEmitter,
         SafeLayoutManager,
+        SafeDiagnostics,
         WindowConfig<T>,
     )>,
     event_loop: &EventLoop<WindowId>,
@@ -120,7 +134,7 @@ pub fn create_windows_from_config<T: Clone>(
 ) -> Arc<Mutex<Vec<SharedWindow<T>>>> {
     let wins = Arc::new(Mutex::new(vec![]));
 
-    for (skia_dom, event_emitter, layout_memorizer, win_config) in windows_config {
+    for (skia_dom, event_emitter, layout_memorizer, diagnostics, win_config) in windows_config {
         let events_processor = EventsProcessor::default();
         let freya_events = Arc::new(Mutex::new(Vec::new()));
         let wb = WindowBuilder::new()
@@ -173,11 +187,13 @@ pub fn create_windows_from_config<T: Clone>(
             freya_events,
             event_emitter,
             font_collection: font_collection.clone(),
+            images_cache: ImagesCache::default(),
             events_processor,
             is_resizing: Arc::new(Mutex::new(false)),
             resizing_timer: Arc::new(Mutex::new(Instant::now())),
             win_config,
             layout_memorizer,
+            diagnostics,
         };
 
         let proxy = event_loop.create_proxy();
@@ -1,3 +1,54 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use skia_safe::Image;
+
+/// Callback invoked with every frame rendered by the [`RenderBackend::Raster`] backend.
+pub type FrameCallback = Arc<dyn Fn(&Image) + Send + Sync>;
+
+/// Backend used to paint a window.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RenderBackend {
+    /// Render into an OpenGL-backed window.
+    #[default]
+    Gpu,
+    /// Render into an offscreen CPU raster surface, no window or GL context is created.
+    Raster,
+}
+
+/// Handle to resize or close a window rendered with the [`RenderBackend::Raster`] backend from outside its render loop.
+#[derive(Clone, Default)]
+pub struct OffscreenController {
+    closed: Arc<AtomicBool>,
+    size: Arc<Mutex<Option<(u32, u32)>>>,
+}
+
+impl OffscreenController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop rendering the window, the render loop returns once all of its windows are closed
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Resize the window before its next frame
+    pub fn resize(&self, width: u32, height: u32) {
+        *self.size.lock().unwrap() = Some((width, height));
+    }
+
+    pub(crate) fn take_size(&self) -> Option<(u32, u32)> {
+        self.size.lock().unwrap().take()
+    }
+}
+
 /// Configuration for a window.
 #[derive(Clone)]
 pub struct WindowConfig<T: Clone> {
@@ -7,6 +58,9 @@ pub struct WindowConfig<T: Clone> {
     pub title: &'static str,
     pub transparent: bool,
     pub state: Option<T>,
+    pub backend: RenderBackend,
+    pub on_frame: Option<FrameCallback>,
+    pub controller: Option<OffscreenController>,
 }
 
 impl<T: Clone> Default for WindowConfig<T> {
@@ -18,6 +72,9 @@ impl<T: Clone> Default for WindowConfig<T> {
             title: "Freya app",
             transparent: false,
             state: None,
+            backend: RenderBackend::Gpu,
+            on_frame: None,
+            controller: None,
         }
     }
 }
@@ -37,6 +94,9 @@ pub struct WindowConfigBuilder<T> {
     pub title: &'static str,
     pub transparent: bool,
     pub state: Option<T>,
+    pub backend: RenderBackend,
+    pub on_frame: Option<FrameCallback>,
+    pub controller: Option<OffscreenController>,
 }
 
 impl<T> Default for WindowConfigBuilder<T> {
@@ -48,6 +108,9 @@ impl<T> Default for WindowConfigBuilder<T> {
             title: "Freya app",
             transparent: false,
             state: None,
+            backend: RenderBackend::Gpu,
+            on_frame: None,
+            controller: None,
         }
     }
 }
@@ -83,6 +146,21 @@ impl<T: Clone> WindowConfigBuilder<T> {
         self
     }
 
+    pub fn with_backend(mut self, backend: RenderBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn with_on_frame(mut self, on_frame: impl Fn(&Image) + Send + Sync + 'static) -> Self {
+        self.on_frame = Some(Arc::new(on_frame));
+        self
+    }
+
+    pub fn with_controller(mut self, controller: OffscreenController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+
     pub fn build(self) -> WindowConfig<T> {
         WindowConfig {
             width: self.width,
@@ -91,6 +169,9 @@ impl<T: Clone> WindowConfigBuilder<T> {
             decorations: self.decorations,
             transparent: self.transparent,
             state: self.state,
+            backend: self.backend,
+            on_frame: self.on_frame,
+            controller: self.controller,
         }
     }
 }
@@ -0,0 +1,403 @@
+use std::fmt::Display;
+
+/// Expression tree of a `calc()` size
+#[derive(Clone, Debug, PartialEq)]
+pub enum CalcType {
+    Manual(f32),
+    Percentage(f32),
+    /// Percentage of the window width
+    ViewportWidth(f32),
+    /// Percentage of the window height
+    ViewportHeight(f32),
+    /// Multiple of the node's font size
+    Em(f32),
+    /// Multiple of the root node's font size
+    Rem(f32),
+    Neg(Box<CalcType>),
+    Add(Box<CalcType>, Box<CalcType>),
+    Sub(Box<CalcType>, Box<CalcType>),
+    Mul(Box<CalcType>, Box<CalcType>),
+    Div(Box<CalcType>, Box<CalcType>),
+    Min(Vec<CalcType>),
+    Max(Vec<CalcType>),
+    /// Clamp the value (second) between a minimum (first) and a maximum (third)
+    Clamp(Box<CalcType>, Box<CalcType>, Box<CalcType>),
+}
+
+impl CalcType {
+    /// Binding power of the expression, used to know when it must be wrapped in parentheses
+    fn precedence(&self) -> u8 {
+        match self {
+            CalcType::Add(..) | CalcType::Sub(..) => 1,
+            CalcType::Mul(..) | CalcType::Div(..) => 2,
+            CalcType::Neg(..) => 3,
+            _ => 4,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut std::fmt::Formatter<'_>, precedence: u8) -> std::fmt::Result {
+        if self.precedence() < precedence {
+            f.write_fmt(format_args!("({self})"))
+        } else {
+            f.write_fmt(format_args!("{self}"))
+        }
+    }
+
+    fn fmt_binary(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: &CalcType,
+        op: &str,
+        rhs: &CalcType,
+    ) -> std::fmt::Result {
+        let precedence = self.precedence();
+        lhs.fmt_operand(f, precedence)?;
+        f.write_fmt(format_args!(" {op} "))?;
+        // The right side of `-` and `/` must keep it's grouping, e.g `10 - (5 - 2)`
+        rhs.fmt_operand(f, precedence + 1)
+    }
+}
+
+impl Display for CalcType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |values: &Vec<CalcType>| {
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        match self {
+            CalcType::Manual(s) => f.write_fmt(format_args!("{s}")),
+            CalcType::Percentage(p) => f.write_fmt(format_args!("{p}%")),
+            CalcType::ViewportWidth(v) => f.write_fmt(format_args!("{v}vw")),
+            CalcType::ViewportHeight(v) => f.write_fmt(format_args!("{v}vh")),
+            CalcType::Em(v) => f.write_fmt(format_args!("{v}em")),
+            CalcType::Rem(v) => f.write_fmt(format_args!("{v}rem")),
+            CalcType::Neg(value) => {
+                f.write_str("-")?;
+                value.fmt_operand(f, self.precedence())
+            }
+            CalcType::Add(lhs, rhs) => self.fmt_binary(f, lhs, "+", rhs),
+            CalcType::Sub(lhs, rhs) => self.fmt_binary(f, lhs, "-", rhs),
+            CalcType::Mul(lhs, rhs) => self.fmt_binary(f, lhs, "*", rhs),
+            CalcType::Div(lhs, rhs) => self.fmt_binary(f, lhs, "/", rhs),
+            CalcType::Min(values) => f.write_fmt(format_args!("min({})", join(values))),
+            CalcType::Max(values) => f.write_fmt(format_args!("max({})", join(values))),
+            CalcType::Clamp(min, value, max) => {
+                f.write_fmt(format_args!("clamp({min}, {value}, {max})"))
+            }
+        }
+    }
+}
+
+/// Reason why a `calc()` expression couldn't be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseCalcError {
+    /// The expression is not wrapped in `calc(...)`
+    NotACalculation,
+    /// The expression ended while a value or a `)` was expected
+    UnexpectedEnd,
+    /// Found a character or token that is not valid in that position
+    UnexpectedToken { position: usize, token: String },
+    /// A number couldn't be parsed
+    InvalidNumber(String),
+    /// Only `px`, `%`, `vw`, `vh`, `em` and `rem` units are supported
+    UnknownUnit(String),
+    /// Only `calc`, `min`, `max` and `clamp` functions are supported
+    UnknownFunction(String),
+    /// A function was called with a wrong amount of arguments
+    WrongArgumentsCount { function: String, found: usize },
+}
+
+impl Display for ParseCalcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCalcError::NotACalculation => f.write_str("expected calc(...)"),
+            ParseCalcError::UnexpectedEnd => f.write_str("unexpected end of the expression"),
+            ParseCalcError::UnexpectedToken { position, token } => {
+                f.write_fmt(format_args!("unexpected `{token}` at {position}"))
+            }
+            ParseCalcError::InvalidNumber(number) => {
+                f.write_fmt(format_args!("invalid number `{number}`"))
+            }
+            ParseCalcError::UnknownUnit(unit) => f.write_fmt(format_args!("unknown unit `{unit}`")),
+            ParseCalcError::UnknownFunction(function) => {
+                f.write_fmt(format_args!("unknown function `{function}`"))
+            }
+            ParseCalcError::WrongArgumentsCount { function, found } => f.write_fmt(format_args!(
+                "wrong amount of arguments ({found}) for `{function}`"
+            )),
+        }
+    }
+}
+
+impl std::error::Error for ParseCalcError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum CalcToken {
+    /// A number followed by it's unit, which might be empty
+    Number(String, String),
+    Function(String),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Comma,
+    OpenParen,
+    CloseParen,
+}
+
+impl Display for CalcToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalcToken::Number(n, unit) => f.write_fmt(format_args!("{n}{unit}")),
+            CalcToken::Function(name) => f.write_str(name),
+            CalcToken::Add => f.write_str("+"),
+            CalcToken::Sub => f.write_str("-"),
+            CalcToken::Mul => f.write_str("*"),
+            CalcToken::Div => f.write_str("/"),
+            CalcToken::Comma => f.write_str(","),
+            CalcToken::OpenParen => f.write_str("("),
+            CalcToken::CloseParen => f.write_str(")"),
+        }
+    }
+}
+
+/// Split an expression into tokens with the position where they start
+fn tokenize(value: &str) -> Result<Vec<(usize, CalcToken)>, ParseCalcError> {
+    let mut tokens = Vec::new();
+    let mut chars = value.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => CalcToken::Add,
+            '-' => CalcToken::Sub,
+            '*' => CalcToken::Mul,
+            '/' => CalcToken::Div,
+            ',' => CalcToken::Comma,
+            '(' => CalcToken::OpenParen,
+            ')' => CalcToken::CloseParen,
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                    number.push(c);
+                }
+                let mut unit = String::new();
+                while let Some((_, c)) =
+                    chars.next_if(|(_, c)| c.is_ascii_alphabetic() || *c == '%')
+                {
+                    unit.push(c);
+                }
+                CalcToken::Number(number, unit)
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut name = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_alphabetic()) {
+                    name.push(c);
+                }
+                CalcToken::Function(name)
+            }
+            c => {
+                return Err(ParseCalcError::UnexpectedToken {
+                    position,
+                    token: c.to_string(),
+                })
+            }
+        };
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser of `calc()` expressions
+struct CalcParser {
+    tokens: Vec<(usize, CalcToken)>,
+    current: usize,
+}
+
+impl CalcParser {
+    fn peek(&self) -> Option<&CalcToken> {
+        self.tokens.get(self.current).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<(usize, CalcToken), ParseCalcError> {
+        let token = self
+            .tokens
+            .get(self.current)
+            .cloned()
+            .ok_or(ParseCalcError::UnexpectedEnd)?;
+        self.current += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: CalcToken) -> Result<(), ParseCalcError> {
+        let (position, token) = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(ParseCalcError::UnexpectedToken {
+                position,
+                token: token.to_string(),
+            })
+        }
+    }
+
+    /// expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<CalcType, ParseCalcError> {
+        let mut lhs = self.term()?;
+        loop {
+            match self.peek() {
+                Some(CalcToken::Add) => {
+                    self.current += 1;
+                    lhs = CalcType::Add(Box::new(lhs), Box::new(self.term()?));
+                }
+                Some(CalcToken::Sub) => {
+                    self.current += 1;
+                    lhs = CalcType::Sub(Box::new(lhs), Box::new(self.term()?));
+                }
+                _ => return Ok(lhs),
+            }
+        }
+    }
+
+    /// term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<CalcType, ParseCalcError> {
+        let mut lhs = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(CalcToken::Mul) => {
+                    self.current += 1;
+                    lhs = CalcType::Mul(Box::new(lhs), Box::new(self.unary()?));
+                }
+                Some(CalcToken::Div) => {
+                    self.current += 1;
+                    lhs = CalcType::Div(Box::new(lhs), Box::new(self.unary()?));
+                }
+                _ => return Ok(lhs),
+            }
+        }
+    }
+
+    /// unary := ('-' | '+') unary | primary
+    fn unary(&mut self) -> Result<CalcType, ParseCalcError> {
+        match self.peek() {
+            Some(CalcToken::Sub) => {
+                self.current += 1;
+                Ok(CalcType::Neg(Box::new(self.unary()?)))
+            }
+            Some(CalcToken::Add) => {
+                self.current += 1;
+                self.unary()
+            }
+            _ => self.primary(),
+        }
+    }
+
+    /// primary := number | percentage | '(' expression ')' | function '(' arguments ')'
+    fn primary(&mut self) -> Result<CalcType, ParseCalcError> {
+        let (position, token) = self.next()?;
+        match token {
+            CalcToken::Number(number, unit) => build_length(&number, &unit),
+            CalcToken::OpenParen => {
+                let value = self.expression()?;
+                self.expect(CalcToken::CloseParen)?;
+                Ok(value)
+            }
+            CalcToken::Function(function) => {
+                self.expect(CalcToken::OpenParen)?;
+                let mut arguments = vec![self.expression()?];
+                while self.peek() == Some(&CalcToken::Comma) {
+                    self.current += 1;
+                    arguments.push(self.expression()?);
+                }
+                self.expect(CalcToken::CloseParen)?;
+                build_function(function, arguments)
+            }
+            token => Err(ParseCalcError::UnexpectedToken {
+                position,
+                token: token.to_string(),
+            }),
+        }
+    }
+}
+
+fn build_length(number: &str, unit: &str) -> Result<CalcType, ParseCalcError> {
+    let value = number
+        .parse()
+        .map_err(|_| ParseCalcError::InvalidNumber(number.to_string()))?;
+
+    match unit {
+        "" | "px" => Ok(CalcType::Manual(value)),
+        "%" => Ok(CalcType::Percentage(value)),
+        "vw" => Ok(CalcType::ViewportWidth(value)),
+        "vh" => Ok(CalcType::ViewportHeight(value)),
+        "em" => Ok(CalcType::Em(value)),
+        "rem" => Ok(CalcType::Rem(value)),
+        unit => Err(ParseCalcError::UnknownUnit(unit.to_string())),
+    }
+}
+
+fn build_function(
+    function: String,
+    mut arguments: Vec<CalcType>,
+) -> Result<CalcType, ParseCalcError> {
+    match (function.as_str(), arguments.len()) {
+        ("calc", 1) => Ok(arguments.remove(0)),
+        ("min", _) => Ok(CalcType::Min(arguments)),
+        ("max", _) => Ok(CalcType::Max(arguments)),
+        ("clamp", 3) => {
+            let max = arguments.remove(2);
+            let value = arguments.remove(1);
+            let min = arguments.remove(0);
+            Ok(CalcType::Clamp(
+                Box::new(min),
+                Box::new(value),
+                Box::new(max),
+            ))
+        }
+        ("calc" | "clamp", found) => Err(ParseCalcError::WrongArgumentsCount { function, found }),
+        _ => Err(ParseCalcError::UnknownFunction(function)),
+    }
+}
+
+/// Parse a `calc()` expression, e.g `"calc((100% - 20) / 3)"` or `"calc(clamp(100, 50%, 400) - 10)"`
+pub fn parse_calc(value: &str) -> Result<CalcType, ParseCalcError> {
+    let value = value.trim();
+    if !value.starts_with("calc(") {
+        return Err(ParseCalcError::NotACalculation);
+    }
+
+    let mut parser = CalcParser {
+        tokens: tokenize(value)?,
+        current: 0,
+    };
+    let calc = parser.primary()?;
+
+    // Nothing can follow the closing parenthesis of `calc()`
+    match parser.tokens.get(parser.current) {
+        Some((position, token)) => Err(ParseCalcError::UnexpectedToken {
+            position: *position,
+            token: token.to_string(),
+        }),
+        None => Ok(calc),
+    }
+}
+
+/// Parse a single number with an optional unit, e.g `"10"`, `"10px"`, `"50vw"` or `"1.5rem"`
+pub fn parse_length(value: &str) -> Result<CalcType, ParseCalcError> {
+    let mut tokens = tokenize(value)?.into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some((_, CalcToken::Number(number, unit))), None) => build_length(&number, &unit),
+        (_, Some((position, token))) | (Some((position, token)), None) => {
+            Err(ParseCalcError::UnexpectedToken {
+                position,
+                token: token.to_string(),
+            })
+        }
+        (None, None) => Err(ParseCalcError::UnexpectedEnd),
+    }
+}
@@ -0,0 +1,285 @@
+use skia_safe::Color;
+
+/// Parse a color, e.g `red`, `#ff0000`, `rgb(255, 0, 0)`, `rgba(255, 0, 0, 0.5)` or `hsl(0, 100%, 50%)`.
+/// `inherit` is not a color, so it's parsed as `None`.
+pub fn parse_color(color: &str) -> Option<Color> {
+    let color = color.trim();
+    if color == "inherit" {
+        None
+    } else if let Some(hex) = color.strip_prefix('#') {
+        parse_hex(hex)
+    } else if color.starts_with("rgb") {
+        parse_rgb(color)
+    } else if color.starts_with("hsl") {
+        parse_hsl(color)
+    } else {
+        parse_named_color(color)
+    }
+}
+
+/// Parse the digits of a hex color in the `rgb`, `rgba`, `rrggbb` or `rrggbbaa` forms
+pub fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+    match hex.len() {
+        3 => Some(Color::from_rgb(digit(0)?, digit(1)?, digit(2)?)),
+        4 => Some(Color::from_argb(digit(3)?, digit(0)?, digit(1)?, digit(2)?)),
+        6 => Some(Color::from_rgb(pair(0)?, pair(2)?, pair(4)?)),
+        8 => Some(Color::from_argb(pair(6)?, pair(0)?, pair(2)?, pair(4)?)),
+        _ => None,
+    }
+}
+
+/// Parse a `rgb(r, g, b)` or `rgba(r, g, b, a)` color.
+/// The alpha of `rgba()` goes from `0.0` to `1.0` (or `0%` to `100%`),
+/// a fourth integer in `rgb()` is an alpha from `0` to `255`.
+pub fn parse_rgb(color: &str) -> Option<Color> {
+    let (is_rgba, args) = if let Some(args) = color.strip_prefix("rgba(") {
+        (true, args)
+    } else {
+        (false, color.strip_prefix("rgb(")?)
+    };
+    let mut colors = split_color_args(args);
+
+    let r = colors.next()?.parse().ok()?;
+    let g = colors.next()?.parse().ok()?;
+    let b = colors.next()?.parse().ok()?;
+    let a = match colors.next() {
+        Some(a) if !is_rgba && !a.contains(['.', '%']) => a.parse::<u8>().ok()?,
+        Some(a) => parse_alpha(a)?,
+        None => 255,
+    };
+
+    if colors.next().is_some() {
+        return None;
+    }
+
+    Some(Color::from_argb(a, r, g, b))
+}
+
+/// Parse a `hsl(h, s%, l%)` or `hsla(h, s%, l%, a)` color.
+/// The hue is in degrees, and the alpha goes from `0.0` to `1.0` (or `0%` to `100%`).
+pub fn parse_hsl(color: &str) -> Option<Color> {
+    let args = color
+        .strip_prefix("hsla(")
+        .or_else(|| color.strip_prefix("hsl("))?;
+    let mut values = split_color_args(args);
+
+    let hue = values.next()?;
+    let hue = hue.strip_suffix("deg").unwrap_or(hue).parse::<f32>().ok()?;
+    let saturation = parse_percentage(values.next()?)?;
+    let lightness = parse_percentage(values.next()?)?;
+    let a = match values.next() {
+        Some(a) => parse_alpha(a)?,
+        None => 255,
+    };
+
+    if values.next().is_some() || !hue.is_finite() {
+        return None;
+    }
+
+    // https://www.w3.org/TR/css-color-4/#hsl-to-rgb
+    let hue = hue.rem_euclid(360.0);
+    let channel = |n: f32| {
+        let k = (n + hue / 30.0) % 12.0;
+        let amount = saturation * lightness.min(1.0 - lightness);
+        let value = lightness - amount * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+        (value * 255.0).round() as u8
+    };
+
+    Some(Color::from_argb(
+        a,
+        channel(0.0),
+        channel(8.0),
+        channel(4.0),
+    ))
+}
+
+/// Split the arguments of a color function, separated by commas, spaces or a slash before the alpha.
+/// A missing closing parenthesis is tolerated.
+fn split_color_args(args: &str) -> impl Iterator<Item = &str> {
+    args.trim_end()
+        .trim_end_matches(')')
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+}
+
+/// Parse a `0%` to `100%` percentage as a `0.0` to `1.0` value
+fn parse_percentage(value: &str) -> Option<f32> {
+    let value = value.strip_suffix('%')?.parse::<f32>().ok()?;
+    value.is_finite().then(|| (value / 100.0).clamp(0.0, 1.0))
+}
+
+/// Parse an alpha from `0.0` to `1.0` (or `0%` to `100%`) into a `0` to `255` value
+fn parse_alpha(value: &str) -> Option<u8> {
+    let alpha = if value.ends_with('%') {
+        parse_percentage(value)?
+    } else {
+        let value = value.parse::<f32>().ok()?;
+        value.is_finite().then(|| value.clamp(0.0, 1.0))?
+    };
+    Some((alpha * 255.0).round() as u8)
+}
+
+/// Parse one of the CSS named colors, or `transparent`
+pub fn parse_named_color(name: &str) -> Option<Color> {
+    let name = name.to_ascii_lowercase();
+    let (r, g, b) = match name.as_str() {
+        "transparent" => return Some(Color::TRANSPARENT),
+        "aliceblue" => (240, 248, 255),
+        "antiquewhite" => (250, 235, 215),
+        "aqua" => (0, 255, 255),
+        "aquamarine" => (127, 255, 212),
+        "azure" => (240, 255, 255),
+        "beige" => (245, 245, 220),
+        "bisque" => (255, 228, 196),
+        "black" => (0, 0, 0),
+        "blanchedalmond" => (255, 235, 205),
+        "blue" => (0, 0, 255),
+        "blueviolet" => (138, 43, 226),
+        "brown" => (165, 42, 42),
+        "burlywood" => (222, 184, 135),
+        "cadetblue" => (95, 158, 160),
+        "chartreuse" => (127, 255, 0),
+        "chocolate" => (210, 105, 30),
+        "coral" => (255, 127, 80),
+        "cornflowerblue" => (100, 149, 237),
+        "cornsilk" => (255, 248, 220),
+        "crimson" => (220, 20, 60),
+        "cyan" => (0, 255, 255),
+        "darkblue" => (0, 0, 139),
+        "darkcyan" => (0, 139, 139),
+        "darkgoldenrod" => (184, 134, 11),
+        "darkgray" => (169, 169, 169),
+        "darkgreen" => (0, 100, 0),
+        "darkgrey" => (169, 169, 169),
+        "darkkhaki" => (189, 183, 107),
+        "darkmagenta" => (139, 0, 139),
+        "darkolivegreen" => (85, 107, 47),
+        "darkorange" => (255, 140, 0),
+        "darkorchid" => (153, 50, 204),
+        "darkred" => (139, 0, 0),
+        "darksalmon" => (233, 150, 122),
+        "darkseagreen" => (143, 188, 143),
+        "darkslateblue" => (72, 61, 139),
+        "darkslategray" => (47, 79, 79),
+        "darkslategrey" => (47, 79, 79),
+        "darkturquoise" => (0, 206, 209),
+        "darkviolet" => (148, 0, 211),
+        "deeppink" => (255, 20, 147),
+        "deepskyblue" => (0, 191, 255),
+        "dimgray" => (105, 105, 105),
+        "dimgrey" => (105, 105, 105),
+        "dodgerblue" => (30, 144, 255),
+        "firebrick" => (178, 34, 34),
+        "floralwhite" => (255, 250, 240),
+        "forestgreen" => (34, 139, 34),
+        "fuchsia" => (255, 0, 255),
+        "gainsboro" => (220, 220, 220),
+        "ghostwhite" => (248, 248, 255),
+        "gold" => (255, 215, 0),
+        "goldenrod" => (218, 165, 32),
+        "gray" => (128, 128, 128),
+        "green" => (0, 128, 0),
+        "greenyellow" => (173, 255, 47),
+        "grey" => (128, 128, 128),
+        "honeydew" => (240, 255, 240),
+        "hotpink" => (255, 105, 180),
+        "indianred" => (205, 92, 92),
+        "indigo" => (75, 0, 130),
+        "ivory" => (255, 255, 240),
+        "khaki" => (240, 230, 140),
+        "lavender" => (230, 230, 250),
+        "lavenderblush" => (255, 240, 245),
+        "lawngreen" => (124, 252, 0),
+        "lemonchiffon" => (255, 250, 205),
+        "lightblue" => (173, 216, 230),
+        "lightcoral" => (240, 128, 128),
+        "lightcyan" => (224, 255, 255),
+        "lightgoldenrodyellow" => (250, 250, 210),
+        "lightgray" => (211, 211, 211),
+        "lightgreen" => (144, 238, 144),
+        "lightgrey" => (211, 211, 211),
+        "lightpink" => (255, 182, 193),
+        "lightsalmon" => (255, 160, 122),
+        "lightseagreen" => (32, 178, 170),
+        "lightskyblue" => (135, 206, 250),
+        "lightslategray" => (119, 136, 153),
+        "lightslategrey" => (119, 136, 153),
+        "lightsteelblue" => (176, 196, 222),
+        "lightyellow" => (255, 255, 224),
+        "lime" => (0, 255, 0),
+        "limegreen" => (50, 205, 50),
+        "linen" => (250, 240, 230),
+        "magenta" => (255, 0, 255),
+        "maroon" => (128, 0, 0),
+        "mediumaquamarine" => (102, 205, 170),
+        "mediumblue" => (0, 0, 205),
+        "mediumorchid" => (186, 85, 211),
+        "mediumpurple" => (147, 112, 219),
+        "mediumseagreen" => (60, 179, 113),
+        "mediumslateblue" => (123, 104, 238),
+        "mediumspringgreen" => (0, 250, 154),
+        "mediumturquoise" => (72, 209, 204),
+        "mediumvioletred" => (199, 21, 133),
+        "midnightblue" => (25, 25, 112),
+        "mintcream" => (245, 255, 250),
+        "mistyrose" => (255, 228, 225),
+        "moccasin" => (255, 228, 181),
+        "navajowhite" => (255, 222, 173),
+        "navy" => (0, 0, 128),
+        "oldlace" => (253, 245, 230),
+        "olive" => (128, 128, 0),
+        "olivedrab" => (107, 142, 35),
+        "orange" => (255, 165, 0),
+        "orangered" => (255, 69, 0),
+        "orchid" => (218, 112, 214),
+        "palegoldenrod" => (238, 232, 170),
+        "palegreen" => (152, 251, 152),
+        "paleturquoise" => (175, 238, 238),
+        "palevioletred" => (219, 112, 147),
+        "papayawhip" => (255, 239, 213),
+        "peachpuff" => (255, 218, 185),
+        "peru" => (205, 133, 63),
+        "pink" => (255, 192, 203),
+        "plum" => (221, 160, 221),
+        "powderblue" => (176, 224, 230),
+        "purple" => (128, 0, 128),
+        "rebeccapurple" => (102, 51, 153),
+        "red" => (255, 0, 0),
+        "rosybrown" => (188, 143, 143),
+        "royalblue" => (65, 105, 225),
+        "saddlebrown" => (139, 69, 19),
+        "salmon" => (250, 128, 114),
+        "sandybrown" => (244, 164, 96),
+        "seagreen" => (46, 139, 87),
+        "seashell" => (255, 245, 238),
+        "sienna" => (160, 82, 45),
+        "silver" => (192, 192, 192),
+        "skyblue" => (135, 206, 235),
+        "slateblue" => (106, 90, 205),
+        "slategray" => (112, 128, 144),
+        "slategrey" => (112, 128, 144),
+        "snow" => (255, 250, 250),
+        "springgreen" => (0, 255, 127),
+        "steelblue" => (70, 130, 180),
+        "tan" => (210, 180, 140),
+        "teal" => (0, 128, 128),
+        "thistle" => (216, 191, 216),
+        "tomato" => (255, 99, 71),
+        "turquoise" => (64, 224, 208),
+        "violet" => (238, 130, 238),
+        "wheat" => (245, 222, 179),
+        "white" => (255, 255, 255),
+        "whitesmoke" => (245, 245, 245),
+        "yellow" => (255, 255, 0),
+        "yellowgreen" => (154, 205, 50),
+        _ => return None,
+    };
+    Some(Color::from_rgb(r, g, b))
+}
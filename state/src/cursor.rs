@@ -3,10 +3,10 @@ use dioxus_native_core::state::ParentDepState;
 use dioxus_native_core_macro::sorted_str_slice;
 use skia_safe::Color;
 
-use crate::parse_color;
+use crate::{parse_color, AttributeErrorReason, AttributeErrors, SafeDiagnostics, UnknownKeyword};
 
 impl ParentDepState for CursorSettings {
-    type Ctx = ();
+    type Ctx = SafeDiagnostics;
     type DepState = Self;
 
     const NODE_MASK: NodeMask =
@@ -15,53 +15,78 @@ impl ParentDepState for CursorSettings {
             "cursor_color",
             "cursor_mode",
             "cursor_id",
-        ])));
+        ])))
+        .with_tag();
 
     fn reduce<'a>(
         &mut self,
         node: NodeView,
         parent: Option<&'a Self::DepState>,
-        _ctx: &Self::Ctx,
+        ctx: &Self::Ctx,
     ) -> bool {
         let mut cursor = parent.cloned().unwrap_or_default();
+        let mut errors = AttributeErrors::new(&node, "cursor");
 
         for attr in node.attributes() {
             match attr.name {
                 "cursor_index" => {
-                    let text = attr.value.as_text().unwrap();
-                    if text != "none" {
-                        let new_cursor_index = text.parse().unwrap();
-                        cursor.position = Some(new_cursor_index);
+                    let value = attr.value.to_string();
+                    if value != "none" {
+                        if let Ok(new_cursor_index) = value.parse() {
+                            cursor.position = Some(new_cursor_index);
+                        } else {
+                            errors.invalid_value(attr.name, &value);
+                        }
                     }
                 }
                 "cursor_color" => {
-                    let new_cursor_color = parse_color(&attr.value.to_string());
+                    let value = attr.value.to_string();
+                    let new_cursor_color = parse_color(&value);
                     if let Some(new_cursor_color) = new_cursor_color {
                         cursor.color = new_cursor_color;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "cursor_mode" => {
-                    cursor.mode = parse_cursor(&attr.value.to_string());
+                    let value = attr.value.to_string();
+                    if let Ok(new_cursor_mode) = try_parse_cursor_mode(&value) {
+                        cursor.mode = new_cursor_mode;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
                 }
                 "cursor_id" => {
-                    let new_cursor_id = attr.value.to_string().parse();
-                    if let Ok(new_cursor_id) = new_cursor_id {
+                    let value = attr.value.to_string();
+                    if let Ok(new_cursor_id) = value.parse() {
                         cursor.id = Some(new_cursor_id);
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
-                _ => {}
+                _ => {
+                    errors.push(
+                        attr.name,
+                        &attr.value.to_string(),
+                        AttributeErrorReason::Unsupported,
+                    );
+                }
             }
         }
+
+        errors.report(ctx);
         let changed = &cursor != self;
         *self = cursor;
         changed
     }
 }
 
-fn parse_cursor(cursor: &str) -> CursorMode {
-    match cursor {
-        "editable" => CursorMode::Editable,
-        _ => CursorMode::None,
+/// Parse a cursor mode, use `.unwrap_or_default()` to fallback to the default one
+pub fn try_parse_cursor_mode(value: &str) -> Result<CursorMode, UnknownKeyword> {
+    match value {
+        "none" => Ok(CursorMode::None),
+        "editable" => Ok(CursorMode::Editable),
+        _ => Err(UnknownKeyword(value.to_string())),
     }
 }
 
@@ -84,8 +109,9 @@ impl Default for CursorSettings {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub enum CursorMode {
+    #[default]
     None,
     Editable,
 }
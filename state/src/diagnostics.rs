@@ -0,0 +1,197 @@
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+use dioxus_core::ElementId;
+use dioxus_native_core::node_ref::NodeView;
+use dioxus_native_core::real_dom::RealDom;
+use tracing::warn;
+
+use crate::{NodeState, ParseCalcError};
+
+/// Diagnostics of a DOM, shared with it's states through the state context
+pub type SafeDiagnostics = Arc<Mutex<Diagnostics>>;
+
+/// Why the attribute of a node couldn't be applied
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttributeErrorReason {
+    /// The attribute is not supported by the element
+    Unsupported,
+    /// The value couldn't be parsed
+    InvalidValue,
+    /// The value is not a valid size, length or `calc()` expression
+    InvalidCalculation(ParseCalcError),
+    /// The value is not of the expected type, e.g text instead of bytes
+    UnexpectedType,
+    /// The resource the value points to couldn't be loaded, e.g a missing image file
+    LoadFailed(String),
+}
+
+impl Display for AttributeErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeErrorReason::Unsupported => f.write_str("unsupported attribute"),
+            AttributeErrorReason::InvalidValue => f.write_str("invalid value"),
+            AttributeErrorReason::InvalidCalculation(error) => {
+                f.write_fmt(format_args!("invalid value, {error}"))
+            }
+            AttributeErrorReason::UnexpectedType => f.write_str("unexpected value type"),
+            AttributeErrorReason::LoadFailed(reason) => {
+                f.write_fmt(format_args!("couldn't be loaded, {reason}"))
+            }
+        }
+    }
+}
+
+/// The value of an attribute is not one of the keywords it accepts, e.g `wrap: "freya"`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownKeyword(pub String);
+
+impl Display for UnknownKeyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("unknown keyword `{}`", self.0))
+    }
+}
+
+impl std::error::Error for UnknownKeyword {}
+
+/// An attribute that was ignored while reducing the state of a node
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeError {
+    pub node_id: usize,
+    pub element: String,
+    pub attribute: String,
+    pub value: String,
+    pub reason: AttributeErrorReason,
+    /// Name of the state that reported the error, e.g `size`
+    pub state: &'static str,
+}
+
+impl AttributeError {
+    pub fn new(
+        node: &NodeView,
+        state: &'static str,
+        attribute: &str,
+        value: &str,
+        reason: AttributeErrorReason,
+    ) -> Self {
+        Self {
+            node_id: node.id().0,
+            element: node.tag().unwrap_or_default().to_string(),
+            attribute: attribute.to_string(),
+            value: value.to_string(),
+            reason,
+            state,
+        }
+    }
+}
+
+impl Display for AttributeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "<{}> {}: \"{}\" ({})",
+            self.element, self.attribute, self.value, self.reason
+        ))
+    }
+}
+
+/// Attribute errors of the nodes of a DOM
+#[derive(Default, Debug)]
+pub struct Diagnostics {
+    errors: Vec<AttributeError>,
+}
+
+impl Diagnostics {
+    /// Get the attribute errors of all the nodes
+    pub fn errors(&self) -> &[AttributeError] {
+        &self.errors
+    }
+
+    /// Report an error found outside of the states, like while rendering.
+    /// It's forgotten once the state that owns the attribute reduces the node again.
+    pub fn report(&mut self, error: AttributeError) {
+        if !self.errors.contains(&error) {
+            warn_error(&error);
+            self.errors.push(error);
+        }
+    }
+
+    /// Replace the errors previously reported by a state for a node
+    fn replace(&mut self, node_id: usize, state: &'static str, errors: Vec<AttributeError>) {
+        self.errors
+            .retain(|error| error.node_id != node_id || error.state != state);
+        self.errors.extend(errors);
+    }
+
+    /// Forget the errors of the nodes that were removed from the DOM
+    pub fn remove_missing_nodes(&mut self, rdom: &RealDom<NodeState>) {
+        self.errors
+            .retain(|error| rdom.get(ElementId(error.node_id)).is_some());
+    }
+
+    /// Forget all the reported attribute errors
+    pub fn clear(&mut self) {
+        self.errors.clear();
+    }
+}
+
+fn warn_error(error: &AttributeError) {
+    warn!(
+        element = %error.element,
+        attribute = %error.attribute,
+        value = %error.value,
+        reason = %error.reason,
+        "Ignored attribute"
+    );
+}
+
+/// Collects the attribute errors found by a state while reducing a node
+pub(crate) struct AttributeErrors<'a> {
+    node: &'a NodeView<'a>,
+    state: &'static str,
+    errors: Vec<AttributeError>,
+}
+
+impl<'a> AttributeErrors<'a> {
+    pub(crate) fn new(node: &'a NodeView<'a>, state: &'static str) -> Self {
+        Self {
+            node,
+            state,
+            errors: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, attribute: &str, value: &str, reason: AttributeErrorReason) {
+        self.errors.push(AttributeError::new(
+            self.node, self.state, attribute, value, reason,
+        ));
+    }
+
+    pub(crate) fn invalid_value(&mut self, attribute: &str, value: &str) {
+        self.push(attribute, value, AttributeErrorReason::InvalidValue);
+    }
+
+    pub(crate) fn invalid_calculation(
+        &mut self,
+        attribute: &str,
+        value: &str,
+        error: ParseCalcError,
+    ) {
+        self.push(
+            attribute,
+            value,
+            AttributeErrorReason::InvalidCalculation(error),
+        );
+    }
+
+    /// Replace the errors previously reported by this state for this node
+    pub(crate) fn report(self, diagnostics: &SafeDiagnostics) {
+        for error in &self.errors {
+            warn_error(error);
+        }
+
+        diagnostics
+            .lock()
+            .unwrap()
+            .replace(self.node.id().0, self.state, self.errors);
+    }
+}
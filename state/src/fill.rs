@@ -0,0 +1,280 @@
+use std::fmt::Display;
+
+use skia_safe::Color;
+
+use crate::{parse_color, split_outside_parentheses};
+
+/// How the background of a node is painted
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fill {
+    Color(Color),
+    LinearGradient(LinearGradient),
+    RadialGradient(RadialGradient),
+    ConicGradient(ConicGradient),
+}
+
+impl Default for Fill {
+    fn default() -> Self {
+        Self::Color(Color::TRANSPARENT)
+    }
+}
+
+impl Fill {
+    /// Whether painting this fill would have no visible effect
+    pub fn is_transparent(&self) -> bool {
+        let stops = match self {
+            Fill::Color(color) => return color.a() == 0,
+            Fill::LinearGradient(gradient) => &gradient.stops,
+            Fill::RadialGradient(gradient) => &gradient.stops,
+            Fill::ConicGradient(gradient) => &gradient.stops,
+        };
+        stops.iter().all(|stop| stop.color.a() == 0)
+    }
+}
+
+impl From<Color> for Fill {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl Display for Fill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fill::Color(color) => write_color(f, color),
+            Fill::LinearGradient(gradient) => {
+                f.write_fmt(format_args!("linear-gradient({}deg", gradient.angle))?;
+                write_stops(f, &gradient.stops)
+            }
+            Fill::RadialGradient(gradient) => {
+                f.write_str("radial-gradient(circle")?;
+                write_stops(f, &gradient.stops)
+            }
+            Fill::ConicGradient(gradient) => {
+                f.write_fmt(format_args!("conic-gradient(from {}deg", gradient.angle))?;
+                write_stops(f, &gradient.stops)
+            }
+        }
+    }
+}
+
+pub(crate) fn write_color(f: &mut std::fmt::Formatter<'_>, color: &Color) -> std::fmt::Result {
+    f.write_fmt(format_args!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        color.r(),
+        color.g(),
+        color.b(),
+        color.a()
+    ))
+}
+
+fn write_stops(f: &mut std::fmt::Formatter<'_>, stops: &[GradientStop]) -> std::fmt::Result {
+    for stop in stops {
+        f.write_str(", ")?;
+        write_color(f, &stop.color)?;
+        f.write_fmt(format_args!(" {}%", stop.offset * 100.0))?;
+    }
+    f.write_str(")")
+}
+
+/// A color of a gradient, placed at an offset from `0.0` (start) to `1.0` (end)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    pub color: Color,
+    pub offset: f32,
+}
+
+/// Gradient along a line that goes through the center of the node
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearGradient {
+    /// Direction of the line in degrees, `0` goes to the top and `90` to the right
+    pub angle: f32,
+    pub stops: Vec<GradientStop>,
+}
+
+/// Circular gradient from the center of the node to its farthest corner
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadialGradient {
+    pub stops: Vec<GradientStop>,
+}
+
+/// Gradient rotating around the center of the node
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConicGradient {
+    /// Where the gradient starts in degrees, `0` is the top and it goes clockwise
+    pub angle: f32,
+    pub stops: Vec<GradientStop>,
+}
+
+/// Parse a background, either a color or a gradient like
+/// `linear-gradient(to right, red, blue 80%)`, `radial-gradient(circle, white, black)`
+/// or `conic-gradient(from 90deg, red, yellow, red)`.
+pub fn parse_fill(value: &str) -> Option<Fill> {
+    let value = value.trim();
+    if let Some(args) = gradient_args(value, "linear-gradient") {
+        let (angle, stops) = match args.first().and_then(|arg| parse_direction(arg)) {
+            Some(angle) => (angle, &args[1..]),
+            None => (180.0, &args[..]),
+        };
+        Some(Fill::LinearGradient(LinearGradient {
+            angle,
+            stops: parse_stops(stops)?,
+        }))
+    } else if let Some(args) = gradient_args(value, "radial-gradient") {
+        let stops = match args.first() {
+            Some(&"circle") => &args[1..],
+            _ => &args[..],
+        };
+        Some(Fill::RadialGradient(RadialGradient {
+            stops: parse_stops(stops)?,
+        }))
+    } else if let Some(args) = gradient_args(value, "conic-gradient") {
+        let from = args
+            .first()
+            .and_then(|arg| arg.strip_prefix("from "))
+            .map(|angle| parse_angle(angle.trim()));
+        let (angle, stops) = match from {
+            Some(angle) => (angle?, &args[1..]),
+            None => (0.0, &args[..]),
+        };
+        Some(Fill::ConicGradient(ConicGradient {
+            angle,
+            stops: parse_stops(stops)?,
+        }))
+    } else {
+        parse_color(value).map(Fill::Color)
+    }
+}
+
+/// Get the comma-separated arguments of a gradient function
+fn gradient_args<'a>(value: &'a str, function: &str) -> Option<Vec<&'a str>> {
+    let args = value
+        .strip_prefix(function)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    Some(
+        split_outside_parentheses(args, ',')
+            .map(|arg| arg.trim())
+            .collect(),
+    )
+}
+
+/// Parse an angle in `deg`, `rad`, `grad` or `turn` into degrees
+pub fn parse_angle(value: &str) -> Option<f32> {
+    let (number, to_degrees) = if let Some(number) = value.strip_suffix("deg") {
+        (number, 1.0)
+    } else if let Some(number) = value.strip_suffix("grad") {
+        (number, 0.9)
+    } else if let Some(number) = value.strip_suffix("rad") {
+        (number, 180.0 / std::f32::consts::PI)
+    } else if let Some(number) = value.strip_suffix("turn") {
+        (number, 360.0)
+    } else {
+        (value, 1.0)
+    };
+    let angle = number.trim().parse::<f32>().ok()? * to_degrees;
+    if angle.is_finite() {
+        Some(angle)
+    } else {
+        None
+    }
+}
+
+/// Parse the direction of a linear gradient, either an angle or keywords like `to top right`.
+/// Corners are always at 45 degrees, regardless of the proportions of the node.
+fn parse_direction(value: &str) -> Option<f32> {
+    let sides = match value.strip_prefix("to ") {
+        Some(sides) => sides,
+        None => return parse_angle(value),
+    };
+
+    let mut x = 0.0;
+    let mut y = 0.0;
+    for side in sides.split_whitespace() {
+        match side {
+            "top" => y -= 1.0,
+            "bottom" => y += 1.0,
+            "left" => x -= 1.0,
+            "right" => x += 1.0,
+            _ => return None,
+        }
+    }
+
+    if x == 0.0 && y == 0.0 {
+        return None;
+    }
+
+    let angle = f32::atan2(x, -y).to_degrees();
+    Some(angle.rem_euclid(360.0))
+}
+
+/// Parse the color stops of a gradient, e.g `red`, `blue 50%`.
+/// Stops without an offset are evenly spread between their neighbours.
+fn parse_stops(values: &[&str]) -> Option<Vec<GradientStop>> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let stops = values
+        .iter()
+        .map(|value| {
+            let mut parts = split_outside_parentheses(value, ' ').filter(|part| !part.is_empty());
+            let color = parse_color(parts.next()?)?;
+            let offset = match parts.next() {
+                Some(offset) => {
+                    let offset = offset.strip_suffix('%')?.parse::<f32>().ok()?;
+                    if !offset.is_finite() {
+                        return None;
+                    }
+                    offset / 100.0
+                }
+                None => f32::NAN,
+            };
+            if parts.next().is_some() {
+                return None;
+            }
+            Some(GradientStop { color, offset })
+        })
+        .collect::<Option<Vec<GradientStop>>>()?;
+
+    Some(resolve_offsets(stops))
+}
+
+/// Give an offset to the stops without one, marked with `NaN`
+fn resolve_offsets(mut stops: Vec<GradientStop>) -> Vec<GradientStop> {
+    let last = stops.len() - 1;
+    if stops[0].offset.is_nan() {
+        stops[0].offset = 0.0;
+    }
+    if stops[last].offset.is_nan() {
+        stops[last].offset = 1.0;
+    }
+
+    // Offsets can't go backwards
+    let mut max_offset = stops[0].offset;
+    for stop in stops.iter_mut() {
+        if !stop.offset.is_nan() {
+            stop.offset = stop.offset.max(max_offset);
+            max_offset = stop.offset;
+        }
+    }
+
+    let mut i = 1;
+    while i < last {
+        if stops[i].offset.is_nan() {
+            let start = i - 1;
+            let end = (i..=last)
+                .find(|j| !stops[*j].offset.is_nan())
+                .unwrap_or(last);
+            let (from, to) = (stops[start].offset, stops[end].offset);
+            for (j, stop) in stops.iter_mut().enumerate().take(end).skip(i) {
+                stop.offset = from + (to - from) * (j - start) as f32 / (end - start) as f32;
+            }
+            i = end;
+        }
+        i += 1;
+    }
+
+    stops
+}
@@ -0,0 +1,64 @@
+/// Effects applied to everything a node draws, its descendants included
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Filters {
+    /// Standard deviation of the gaussian blur, in pixels
+    pub blur: f32,
+    /// `0.0` is black, `1.0` leaves the colors as they are
+    pub brightness: f32,
+    /// `0.0` is fully desaturated, `1.0` leaves the colors as they are
+    pub saturate: f32,
+    /// `0.0` leaves the colors as they are, `1.0` is fully gray
+    pub grayscale: f32,
+}
+
+impl Default for Filters {
+    fn default() -> Self {
+        Self {
+            blur: 0.0,
+            brightness: 1.0,
+            saturate: 1.0,
+            grayscale: 0.0,
+        }
+    }
+}
+
+impl Filters {
+    /// Whether the filters leave the drawing as it is
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the filters change the colors of the drawing
+    pub fn has_color_filter(&self) -> bool {
+        self.brightness != 1.0 || self.saturate != 1.0 || self.grayscale != 0.0
+    }
+}
+
+/// Parse a blur radius in pixels, e.g `"4"` or `"4px"`.
+pub fn parse_blur(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let blur = value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse::<f32>()
+        .ok()?;
+    if blur.is_finite() && blur >= 0.0 {
+        Some(blur)
+    } else {
+        None
+    }
+}
+
+/// Parse the amount of a color filter as a number or a percentage, e.g `"0.5"` or `"50%"`.
+pub fn parse_filter_amount(value: &str) -> Option<f32> {
+    let amount = match value.trim().strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f32>().ok()? / 100.0,
+        None => value.trim().parse::<f32>().ok()?,
+    };
+    if amount.is_finite() && amount >= 0.0 {
+        Some(amount)
+    } else {
+        None
+    }
+}
@@ -1,10 +1,15 @@
+use std::fmt::Display;
+
 use dioxus_native_core::node_ref::{AttributeMask, NodeMask, NodeView};
 use dioxus_native_core::state::ParentDepState;
 use dioxus_native_core_macro::sorted_str_slice;
-use skia_safe::textlayout::TextAlign;
+use skia_safe::textlayout::{self, TextAlign, TextStyle};
 use skia_safe::Color;
 
-use crate::parse_color;
+use crate::{
+    parse_color, split_outside_parentheses, write_color, AttributeErrorReason, AttributeErrors,
+    SafeDiagnostics,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FontStyle {
@@ -15,6 +20,12 @@ pub struct FontStyle {
     pub align: TextAlign,
     pub max_lines: Option<usize>,
     pub font_style: skia_safe::FontStyle,
+    pub decoration: Decoration,
+    /// Extra space between letters, in pixels
+    pub letter_spacing: f32,
+    /// Extra space between words, in pixels
+    pub word_spacing: f32,
+    pub text_shadows: Vec<TextShadow>,
 }
 
 impl Default for FontStyle {
@@ -27,13 +38,146 @@ impl Default for FontStyle {
             align: TextAlign::default(),
             max_lines: None,
             font_style: skia_safe::FontStyle::default(),
+            decoration: Decoration::default(),
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
+            text_shadows: Vec::new(),
+        }
+    }
+}
+
+impl FontStyle {
+    /// Skia style of the text, the line height is left out as it's not used everywhere
+    pub fn text_style(&self) -> TextStyle {
+        let mut text_style = TextStyle::new();
+        text_style
+            .set_font_style(self.font_style)
+            .set_color(self.color)
+            .set_font_size(self.font_size)
+            .set_font_families(&[&self.font_family])
+            .set_letter_spacing(self.letter_spacing)
+            .set_word_spacing(self.word_spacing);
+
+        if self.decoration.is_visible() {
+            let mut decoration_type = textlayout::TextDecoration::NO_DECORATION;
+            decoration_type.set(
+                textlayout::TextDecoration::UNDERLINE,
+                self.decoration.underline,
+            );
+            decoration_type.set(
+                textlayout::TextDecoration::OVERLINE,
+                self.decoration.overline,
+            );
+            decoration_type.set(
+                textlayout::TextDecoration::LINE_THROUGH,
+                self.decoration.line_through,
+            );
+            text_style.set_decoration_type(decoration_type);
+            text_style.set_decoration_style(match self.decoration.style {
+                DecorationStyle::Solid => textlayout::TextDecorationStyle::Solid,
+                DecorationStyle::Double => textlayout::TextDecorationStyle::Double,
+                DecorationStyle::Dotted => textlayout::TextDecorationStyle::Dotted,
+                DecorationStyle::Dashed => textlayout::TextDecorationStyle::Dashed,
+                DecorationStyle::Wavy => textlayout::TextDecorationStyle::Wavy,
+            });
+            // Without a color the lines take the color of the text
+            if let Some(color) = self.decoration.color {
+                text_style.set_decoration_color(color);
+            }
+        }
+
+        for shadow in &self.text_shadows {
+            text_style.add_shadow(textlayout::TextShadow::new(
+                shadow.color,
+                (shadow.x, shadow.y),
+                shadow.blur as f64,
+            ));
         }
+
+        text_style
+    }
+}
+
+/// Lines drawn along the text
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Decoration {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+    /// The color of the text if not specified
+    pub color: Option<Color>,
+    pub style: DecorationStyle,
+}
+
+impl Decoration {
+    pub fn is_visible(&self) -> bool {
+        self.underline || self.overline || self.line_through
+    }
+}
+
+impl Display for Decoration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.is_visible() {
+            return f.write_str("none");
+        }
+
+        let lines = [
+            (self.underline, "underline"),
+            (self.overline, "overline"),
+            (self.line_through, "line-through"),
+        ];
+        for (_, line) in lines.iter().filter(|(visible, _)| *visible) {
+            f.write_fmt(format_args!("{line} "))?;
+        }
+        f.write_fmt(format_args!("{}", self.style))?;
+        if let Some(color) = &self.color {
+            f.write_str(" ")?;
+            write_color(f, color)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecorationStyle {
+    #[default]
+    Solid,
+    Double,
+    Dotted,
+    Dashed,
+    Wavy,
+}
+
+impl Display for DecorationStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecorationStyle::Solid => f.write_str("solid"),
+            DecorationStyle::Double => f.write_str("double"),
+            DecorationStyle::Dotted => f.write_str("dotted"),
+            DecorationStyle::Dashed => f.write_str("dashed"),
+            DecorationStyle::Wavy => f.write_str("wavy"),
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct TextShadow {
+    pub x: f32,
+    pub y: f32,
+    pub blur: f32,
+    pub color: Color,
+}
+
+impl Display for TextShadow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} {} {} ", self.x, self.y, self.blur))?;
+        write_color(f, &self.color)
     }
 }
 
 /// Font style are inherited by default if not specified otherwise by some of the supported attributes.
 impl ParentDepState for FontStyle {
-    type Ctx = ();
+    type Ctx = SafeDiagnostics;
     type DepState = Self;
 
     const NODE_MASK: NodeMask =
@@ -44,75 +188,193 @@ impl ParentDepState for FontStyle {
             "line_height",
             "align",
             "max_lines",
-            "font_style"
-        ])));
+            "font_style",
+            "decoration",
+            "letter_spacing",
+            "word_spacing",
+            "text_shadow"
+        ])))
+        .with_tag();
 
     fn reduce<'a>(
         &mut self,
         node: NodeView,
         parent: Option<&'a Self::DepState>,
-        _ctx: &Self::Ctx,
+        ctx: &Self::Ctx,
     ) -> bool {
         let mut font_style = parent.cloned().unwrap_or_default();
+        let mut errors = AttributeErrors::new(&node, "font_style");
 
         for attr in node.attributes() {
+            let value = attr.value.to_string();
             match attr.name {
                 "color" => {
-                    let new_color = parse_color(&attr.value.to_string());
+                    let new_color = parse_color(&value);
                     if let Some(new_color) = new_color {
                         font_style.color = new_color;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "font_family" => {
-                    font_style.font_family = attr.value.to_string();
+                    font_style.font_family = value;
                 }
                 "font_size" => {
-                    if let Ok(font_size) = attr.value.to_string().parse() {
+                    if let Ok(font_size) = value.parse() {
                         font_style.font_size = font_size;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "line_height" => {
-                    if let Ok(line_height) = attr.value.to_string().parse() {
+                    if let Ok(line_height) = value.parse() {
                         font_style.line_height = line_height;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "align" => {
-                    font_style.align = parse_text_align(&attr.value.to_string());
+                    if let Some(align) = try_parse_text_align(&value) {
+                        font_style.align = align;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
                 }
                 "max_lines" => {
-                    if let Ok(max_lines) = attr.value.to_string().parse() {
+                    if let Ok(max_lines) = value.parse() {
                         font_style.max_lines = Some(max_lines);
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "font_style" => {
-                    font_style.font_style = parse_font_style(&attr.value.to_string());
+                    if let Some(new_font_style) = parse_font_style(&value) {
+                        font_style.font_style = new_font_style;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "decoration" => {
+                    if let Some(decoration) = parse_decoration(&value) {
+                        font_style.decoration = decoration;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "letter_spacing" => {
+                    if let Ok(letter_spacing) = value.parse() {
+                        font_style.letter_spacing = letter_spacing;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "word_spacing" => {
+                    if let Ok(word_spacing) = value.parse() {
+                        font_style.word_spacing = word_spacing;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "text_shadow" => {
+                    if let Some(text_shadows) = parse_text_shadows(&value) {
+                        font_style.text_shadows = text_shadows;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                _ => {
+                    errors.push(attr.name, &value, AttributeErrorReason::Unsupported);
                 }
-                _ => {}
             }
         }
+
+        errors.report(ctx);
         let changed = &font_style != self;
         *self = font_style;
         changed
     }
 }
 
-fn parse_font_style(style: &str) -> skia_safe::FontStyle {
+fn parse_font_style(style: &str) -> Option<skia_safe::FontStyle> {
     match style {
-        "italic" => skia_safe::FontStyle::italic(),
-        "bold" => skia_safe::FontStyle::bold(),
-        "bold-italic" => skia_safe::FontStyle::bold_italic(),
-        _ => skia_safe::FontStyle::default(),
+        "normal" => Some(skia_safe::FontStyle::default()),
+        "italic" => Some(skia_safe::FontStyle::italic()),
+        "bold" => Some(skia_safe::FontStyle::bold()),
+        "bold-italic" => Some(skia_safe::FontStyle::bold_italic()),
+        _ => None,
     }
 }
 
 pub fn parse_text_align(align: &str) -> TextAlign {
+    try_parse_text_align(align).unwrap_or(TextAlign::Left)
+}
+
+/// Parse a text alignment, `None` if the value is not a known one
+pub fn try_parse_text_align(align: &str) -> Option<TextAlign> {
     match align {
-        "center" => TextAlign::Center,
-        "end" => TextAlign::End,
-        "justify" => TextAlign::Justify,
-        "left" => TextAlign::Left,
-        "right" => TextAlign::Right,
-        "start" => TextAlign::Start,
-        _ => TextAlign::Left,
+        "center" => Some(TextAlign::Center),
+        "end" => Some(TextAlign::End),
+        "justify" => Some(TextAlign::Justify),
+        "left" => Some(TextAlign::Left),
+        "right" => Some(TextAlign::Right),
+        "start" => Some(TextAlign::Start),
+        _ => None,
     }
 }
+
+/// Parse the lines, style and color of a decoration in any order, e.g `"underline"`,
+/// `"underline line-through wavy red"`, or `"none"`.
+pub fn parse_decoration(value: &str) -> Option<Decoration> {
+    let mut decoration = Decoration::default();
+    if value.trim() == "none" {
+        return Some(decoration);
+    }
+
+    for value in split_outside_parentheses(value, ' ').filter(|v| !v.is_empty()) {
+        match value {
+            "underline" => decoration.underline = true,
+            "overline" => decoration.overline = true,
+            "line-through" => decoration.line_through = true,
+            "solid" => decoration.style = DecorationStyle::Solid,
+            "double" => decoration.style = DecorationStyle::Double,
+            "dotted" => decoration.style = DecorationStyle::Dotted,
+            "dashed" => decoration.style = DecorationStyle::Dashed,
+            "wavy" => decoration.style = DecorationStyle::Wavy,
+            color => decoration.color = Some(parse_color(color)?),
+        }
+    }
+
+    // A color or style alone would draw nothing
+    if decoration.is_visible() {
+        Some(decoration)
+    } else {
+        None
+    }
+}
+
+/// Parse a comma-separated list of text shadows, e.g `"1 1 2 black, 0 0 8 rgb(0, 120, 255)"`, or `"none"`.
+pub fn parse_text_shadows(value: &str) -> Option<Vec<TextShadow>> {
+    if value.trim() == "none" {
+        return Some(Vec::new());
+    }
+
+    split_outside_parentheses(value, ',')
+        .map(parse_text_shadow)
+        .collect()
+}
+
+/// Parse a text shadow in the form of `"x y blur color"`.
+pub fn parse_text_shadow(value: &str) -> Option<TextShadow> {
+    let mut shadow_values = split_outside_parentheses(value, ' ').filter(|value| !value.is_empty());
+
+    let x = shadow_values.next()?.parse().ok()?;
+    let y = shadow_values.next()?.parse().ok()?;
+    let blur: f32 = shadow_values.next()?.parse().ok()?;
+    let color = parse_color(shadow_values.next()?)?;
+
+    if shadow_values.next().is_some() || blur < 0.0 {
+        return None;
+    }
+
+    Some(TextShadow { x, y, blur, color })
+}
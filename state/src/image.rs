@@ -0,0 +1,176 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+use crate::{parse_position, OriginOffset};
+
+/// How an image is resized to fit the area of its node
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectFit {
+    /// Stretched to fill the whole area
+    #[default]
+    Fill,
+    /// Scaled to fit inside of the area, keeping its aspect ratio
+    Contain,
+    /// Scaled to cover the whole area, keeping its aspect ratio
+    Cover,
+    /// Drawn at its original size
+    None,
+    /// Like `None` or `Contain`, whichever results in a smaller image
+    ScaleDown,
+}
+
+impl ObjectFit {
+    /// Size of an image of the given size once it's fitted in the given area
+    pub fn fit(&self, image: (f32, f32), area: (f32, f32)) -> (f32, f32) {
+        let contain = (area.0 / image.0).min(area.1 / image.1);
+        let scale = match self {
+            ObjectFit::Fill => return area,
+            ObjectFit::Contain => contain,
+            ObjectFit::Cover => (area.0 / image.0).max(area.1 / image.1),
+            ObjectFit::None => 1.0,
+            ObjectFit::ScaleDown => contain.min(1.0),
+        };
+        (image.0 * scale, image.1 * scale)
+    }
+}
+
+impl Display for ObjectFit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectFit::Fill => f.write_str("fill"),
+            ObjectFit::Contain => f.write_str("contain"),
+            ObjectFit::Cover => f.write_str("cover"),
+            ObjectFit::None => f.write_str("none"),
+            ObjectFit::ScaleDown => f.write_str("scale-down"),
+        }
+    }
+}
+
+/// Where a fitted image is placed inside of the area of its node, the center by default.
+/// Percentages are relative to the space left between the image and the area.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectPosition {
+    pub x: OriginOffset,
+    pub y: OriginOffset,
+}
+
+impl Default for ObjectPosition {
+    fn default() -> Self {
+        Self {
+            x: OriginOffset::Percentage(50.0),
+            y: OriginOffset::Percentage(50.0),
+        }
+    }
+}
+
+impl Display for ObjectPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} {}", self.x, self.y))
+    }
+}
+
+/// How the pixels of an image are sampled when it's scaled
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageSampling {
+    /// Sharp pixels, good for pixel art
+    Nearest,
+    #[default]
+    Linear,
+    /// Smoother when downscaling a lot
+    Mipmap,
+    /// Smoother when upscaling a lot
+    Cubic,
+}
+
+impl Display for ImageSampling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageSampling::Nearest => f.write_str("nearest"),
+            ImageSampling::Linear => f.write_str("linear"),
+            ImageSampling::Mipmap => f.write_str("mipmap"),
+            ImageSampling::Cubic => f.write_str("cubic"),
+        }
+    }
+}
+
+/// Insets of the nine-slice grid of an image, in pixels of the source image.
+/// The corners keep their size, the edges are stretched along them and the center is stretched in both directions.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageSlice {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
+impl ImageSlice {
+    /// The insets, in the order of the attribute: left, top, right and bottom
+    pub fn as_tuple(&self) -> (f32, f32, f32, f32) {
+        (
+            self.left as f32,
+            self.top as f32,
+            self.right as f32,
+            self.bottom as f32,
+        )
+    }
+}
+
+pub fn parse_object_fit(value: &str) -> Option<ObjectFit> {
+    match value.trim() {
+        "fill" => Some(ObjectFit::Fill),
+        "contain" => Some(ObjectFit::Contain),
+        "cover" => Some(ObjectFit::Cover),
+        "none" => Some(ObjectFit::None),
+        "scale-down" => Some(ObjectFit::ScaleDown),
+        _ => None,
+    }
+}
+
+/// Parse the position of an image, in pixels, percentages or keywords,
+/// e.g `"center"`, `"top left"` or `"0 25%"`.
+pub fn parse_object_position(value: &str) -> Option<ObjectPosition> {
+    let (x, y) = parse_position(value)?;
+    Some(ObjectPosition { x, y })
+}
+
+pub fn parse_image_sampling(value: &str) -> Option<ImageSampling> {
+    match value.trim() {
+        "nearest" => Some(ImageSampling::Nearest),
+        "linear" => Some(ImageSampling::Linear),
+        "mipmap" => Some(ImageSampling::Mipmap),
+        "cubic" => Some(ImageSampling::Cubic),
+        _ => None,
+    }
+}
+
+/// Parse the nine-slice insets of an image as `"left top right bottom"`, or a single value for all of them, e.g `"16"` or `"8 4 8 12"`.
+pub fn parse_image_slice(value: &str) -> Option<ImageSlice> {
+    let insets = value
+        .split_whitespace()
+        .map(|inset| inset.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+
+    match insets[..] {
+        [inset] => Some(ImageSlice {
+            left: inset,
+            top: inset,
+            right: inset,
+            bottom: inset,
+        }),
+        [left, top, right, bottom] => Some(ImageSlice {
+            left,
+            top,
+            right,
+            bottom,
+        }),
+        _ => None,
+    }
+}
+
+/// Hash of the encoded content of an image, used to share its decoded image between nodes
+pub fn hash_image_data(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
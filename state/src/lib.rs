@@ -2,33 +2,47 @@ use dioxus_native_core::state::{NodeDepState, ParentDepState, State};
 use dioxus_native_core_macro::State;
 use skia_safe::Color;
 
+mod calc;
+mod color;
 mod cursor;
+mod diagnostics;
+mod fill;
+mod filter;
 mod font_style;
+mod image;
 mod references;
 mod scroll;
 mod size;
 mod style;
+mod transform;
 
+pub use calc::*;
+pub use color::*;
 pub use cursor::*;
+pub use diagnostics::*;
+pub use fill::*;
+pub use filter::*;
 pub use font_style::*;
+pub use image::*;
 pub use references::*;
 pub use scroll::*;
 pub use size::*;
 pub use style::*;
+pub use transform::*;
 
 #[derive(Clone, State, Default, Debug)]
 pub struct NodeState {
-    #[parent_dep_state(cursor_settings)]
+    #[parent_dep_state(cursor_settings, SafeDiagnostics)]
     pub cursor_settings: CursorSettings,
-    #[parent_dep_state(references)]
+    #[parent_dep_state(references, SafeDiagnostics)]
     pub references: References,
-    #[parent_dep_state(size, Arc<Mutex<LayoutManager>>)]
+    #[parent_dep_state(size, (Arc<Mutex<LayoutManager>>, SafeDiagnostics))]
     pub size: Size,
-    #[parent_dep_state(scroll, Arc<Mutex<LayoutManager>>)]
+    #[parent_dep_state(scroll, (Arc<Mutex<LayoutManager>>, SafeDiagnostics))]
     pub scroll: Scroll,
-    #[node_dep_state()]
+    #[node_dep_state((), SafeDiagnostics)]
     pub style: Style,
-    #[parent_dep_state(font_style)]
+    #[parent_dep_state(font_style, SafeDiagnostics)]
     pub font_style: FontStyle,
 }
 
@@ -48,12 +62,25 @@ impl NodeState {
 
 pub enum AttributeType<'a> {
     Color(&'a Color),
+    Fill(&'a Fill),
     Size(&'a SizeMode),
+    Tracks(&'a [SizeMode]),
     Measure(f32),
     Measures((f32, f32, f32, f32)),
     Direction(&'a DirectionMode),
+    Wrap(&'a WrapMode),
+    Position(&'a PositionMode),
+    Alignment(&'a AlignmentMode),
     Display(&'a DisplayMode),
-    Shadow(&'a ShadowSettings),
+    Shadows(&'a [ShadowSettings]),
+    Border(&'a Border),
+    Transform(&'a [Transform]),
+    TransformOrigin(&'a TransformOrigin),
+    ObjectFit(&'a ObjectFit),
+    ObjectPosition(&'a ObjectPosition),
+    ImageSampling(&'a ImageSampling),
+    Decoration(&'a Decoration),
+    TextShadows(&'a [TextShadow]),
     Text(&'a str),
 }
 
@@ -86,11 +113,14 @@ impl<'a> Iterator for NodeStateIterator<'a> {
             7 => Some(("padding", AttributeType::Measures(self.state.size.padding))),
             8 => Some((
                 "background",
-                AttributeType::Color(&self.state.style.background),
+                AttributeType::Fill(&self.state.style.background),
             )),
             9 => Some(("display", AttributeType::Display(&self.state.style.display))),
-            10 => Some(("radius", AttributeType::Measure(self.state.style.radius))),
-            11 => Some(("shadow", AttributeType::Shadow(&self.state.style.shadow))),
+            10 => Some((
+                "radius",
+                AttributeType::Measures(self.state.style.radius.as_tuple()),
+            )),
+            11 => Some(("shadow", AttributeType::Shadows(&self.state.style.shadows))),
             12 => Some(("color", AttributeType::Color(&self.state.font_style.color))),
             13 => Some((
                 "font_family",
@@ -112,6 +142,116 @@ impl<'a> Iterator for NodeStateIterator<'a> {
                 "scroll_y",
                 AttributeType::Measure(self.state.scroll.scroll_y),
             )),
+            18 => Some((
+                "main_align",
+                AttributeType::Alignment(&self.state.size.main_align),
+            )),
+            19 => Some((
+                "cross_align",
+                AttributeType::Alignment(&self.state.size.cross_align),
+            )),
+            20 => Some(("row_gap", AttributeType::Measure(self.state.size.row_gap))),
+            21 => Some((
+                "column_gap",
+                AttributeType::Measure(self.state.size.column_gap),
+            )),
+            22 => Some(("margin", AttributeType::Measures(self.state.size.margin))),
+            23 => Some(("wrap", AttributeType::Wrap(&self.state.size.wrap))),
+            24 => Some((
+                "position",
+                AttributeType::Position(&self.state.size.position),
+            )),
+            25 => Some(("top", AttributeType::Size(&self.state.size.top))),
+            26 => Some(("right", AttributeType::Size(&self.state.size.right))),
+            27 => Some(("bottom", AttributeType::Size(&self.state.size.bottom))),
+            28 => Some(("left", AttributeType::Size(&self.state.size.left))),
+            29 => Some((
+                "grid_columns",
+                AttributeType::Tracks(&self.state.size.grid_columns),
+            )),
+            30 => Some((
+                "grid_rows",
+                AttributeType::Tracks(&self.state.size.grid_rows),
+            )),
+            31 => Some((
+                "grid_column_span",
+                AttributeType::Measure(self.state.size.grid_column_span as f32),
+            )),
+            32 => Some((
+                "grid_row_span",
+                AttributeType::Measure(self.state.size.grid_row_span as f32),
+            )),
+            33 => Some((
+                "aspect_ratio",
+                AttributeType::Measure(self.state.size.aspect_ratio.unwrap_or_default()),
+            )),
+            34 => Some(("border", AttributeType::Border(&self.state.style.border))),
+            35 => Some((
+                "opacity",
+                AttributeType::Measure(self.state.style.opacity.unwrap_or(1.0)),
+            )),
+            36 => Some((
+                "transform",
+                AttributeType::Transform(&self.state.style.transform),
+            )),
+            37 => Some((
+                "transform_origin",
+                AttributeType::TransformOrigin(&self.state.style.transform_origin),
+            )),
+            38 => Some((
+                "blur",
+                AttributeType::Measure(self.state.style.filters.blur),
+            )),
+            39 => Some((
+                "backdrop_blur",
+                AttributeType::Measure(self.state.style.backdrop_blur),
+            )),
+            40 => Some((
+                "brightness",
+                AttributeType::Measure(self.state.style.filters.brightness),
+            )),
+            41 => Some((
+                "saturate",
+                AttributeType::Measure(self.state.style.filters.saturate),
+            )),
+            42 => Some((
+                "grayscale",
+                AttributeType::Measure(self.state.style.filters.grayscale),
+            )),
+            43 => Some((
+                "object_fit",
+                AttributeType::ObjectFit(&self.state.style.object_fit),
+            )),
+            44 => Some((
+                "object_position",
+                AttributeType::ObjectPosition(&self.state.style.object_position),
+            )),
+            45 => Some((
+                "sampling",
+                AttributeType::ImageSampling(&self.state.style.sampling),
+            )),
+            46 => Some((
+                "slice",
+                AttributeType::Measures(
+                    self.state.style.image_slice.unwrap_or_default().as_tuple(),
+                ),
+            )),
+            47 => Some((
+                "decoration",
+                AttributeType::Decoration(&self.state.font_style.decoration),
+            )),
+            48 => Some((
+                "letter_spacing",
+                AttributeType::Measure(self.state.font_style.letter_spacing),
+            )),
+            49 => Some((
+                "word_spacing",
+                AttributeType::Measure(self.state.font_style.word_spacing),
+            )),
+            50 => Some((
+                "text_shadow",
+                AttributeType::TextShadows(&self.state.font_style.text_shadows),
+            )),
             _ => None,
         }
     }
@@ -123,33 +263,3 @@ impl<'a> Iterator for NodeStateIterator<'a> {
         self.nth(current)
     }
 }
-
-pub fn parse_rgb(color: &str) -> Option<Color> {
-    let color = color.replace("rgb(", "").replace(')', "");
-    let mut colors = color.split(',');
-
-    let r = colors.next()?.trim().parse().ok()?;
-    let g = colors.next()?.trim().parse().ok()?;
-    let b = colors.next()?.trim().parse().ok()?;
-    let a: Option<&str> = colors.next();
-    if let Some(a) = a {
-        let a = a.trim().parse::<u8>().ok()?;
-        Some(Color::from_argb(a, r, g, b))
-    } else {
-        Some(Color::from_rgb(r, g, b))
-    }
-}
-
-pub fn parse_color(color: &str) -> Option<Color> {
-    match color {
-        "inherit" => None,
-        "red" => Some(Color::RED),
-        "green" => Some(Color::GREEN),
-        "blue" => Some(Color::BLUE),
-        "yellow" => Some(Color::YELLOW),
-        "black" => Some(Color::BLACK),
-        "gray" => Some(Color::GRAY),
-        "white" => Some(Color::WHITE),
-        _ => parse_rgb(color),
-    }
-}
@@ -8,6 +8,8 @@ use dioxus_native_core_macro::sorted_str_slice;
 use freya_common::NodeReferenceLayout;
 use tokio::sync::mpsc::UnboundedSender;
 
+use crate::{AttributeErrorReason, AttributeErrors, SafeDiagnostics};
+
 #[derive(Default, Clone, Debug)]
 pub struct References {
     pub node_ref: Option<UnboundedSender<NodeReferenceLayout>>,
@@ -15,20 +17,21 @@ pub struct References {
 }
 
 impl ParentDepState for References {
-    type Ctx = ();
+    type Ctx = SafeDiagnostics;
     type DepState = Self;
 
     const NODE_MASK: NodeMask =
         NodeMask::new_with_attrs(AttributeMask::Static(&sorted_str_slice!([
             "reference",
             "cursor_reference"
-        ])));
+        ])))
+        .with_tag();
 
     fn reduce<'a>(
         &mut self,
         node: NodeView,
         parent: Option<&'a Self::DepState>,
-        _ctx: &Self::Ctx,
+        ctx: &Self::Ctx,
     ) -> bool {
         let mut node_ref = None;
         let mut cursor_ref = if let Some(parent) = parent {
@@ -37,27 +40,56 @@ impl ParentDepState for References {
             None
         };
 
+        let mut errors = AttributeErrors::new(&node, "references");
+
         for a in node.attributes() {
             match a.name {
                 "reference" => {
-                    if let AttributeValue::Any(v) = a.value {
-                        let r: &UseRef<UnboundedSender<NodeReferenceLayout>> =
-                            v.value.downcast_ref().unwrap();
+                    let r: Option<&UseRef<UnboundedSender<NodeReferenceLayout>>> =
+                        if let AttributeValue::Any(v) = a.value {
+                            v.value.downcast_ref()
+                        } else {
+                            None
+                        };
+                    if let Some(r) = r {
                         node_ref = Some(r.read().clone())
+                    } else {
+                        errors.push(
+                            a.name,
+                            &a.value.to_string(),
+                            AttributeErrorReason::UnexpectedType,
+                        );
                     }
                 }
                 "cursor_reference" => {
-                    if let AttributeValue::Any(v) = a.value {
-                        let r: &UseRef<CursorReference> = v.value.downcast_ref().unwrap();
+                    let r: Option<&UseRef<CursorReference>> =
+                        if let AttributeValue::Any(v) = a.value {
+                            v.value.downcast_ref()
+                        } else {
+                            None
+                        };
+                    if let Some(r) = r {
                         cursor_ref = Some(r.read().clone())
+                    } else {
+                        errors.push(
+                            a.name,
+                            &a.value.to_string(),
+                            AttributeErrorReason::UnexpectedType,
+                        );
                     }
                 }
                 _ => {
-                    println!("Unsupported attribute <{}>", a.name);
+                    errors.push(
+                        a.name,
+                        &a.value.to_string(),
+                        AttributeErrorReason::Unsupported,
+                    );
                 }
             }
         }
 
+        errors.report(ctx);
+
         let changed = false;
         *self = Self {
             node_ref,
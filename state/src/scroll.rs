@@ -5,6 +5,8 @@ use dioxus_native_core::state::ParentDepState;
 use dioxus_native_core_macro::sorted_str_slice;
 use freya_common::LayoutMemorizer;
 
+use crate::{AttributeErrorReason, AttributeErrors, SafeDiagnostics};
+
 #[derive(Default, Clone, Debug)]
 pub struct Scroll {
     pub scroll_y: f32,
@@ -14,7 +16,7 @@ pub struct Scroll {
 
 // TODO(marc2332) Why use ParentDepState? NodeDepState might make more sense
 impl ParentDepState for Scroll {
-    type Ctx = Arc<Mutex<LayoutMemorizer>>;
+    type Ctx = (Arc<Mutex<LayoutMemorizer>>, SafeDiagnostics);
     type DepState = Self;
 
     const NODE_MASK: NodeMask =
@@ -33,26 +35,38 @@ impl ParentDepState for Scroll {
         let mut scroll_y = 0.0;
         let mut scroll_x = 0.0;
 
+        let mut errors = AttributeErrors::new(&node, "scroll");
+
         for attr in node.attributes() {
+            let value = attr.value.to_string();
             match attr.name {
                 "scroll_y" => {
-                    let scroll: f32 = attr.value.to_string().parse().unwrap();
-                    scroll_y = scroll;
+                    if let Ok(scroll) = value.parse() {
+                        scroll_y = scroll;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
                 }
                 "scroll_x" => {
-                    let scroll: f32 = attr.value.to_string().parse().unwrap();
-                    scroll_x = scroll;
+                    if let Ok(scroll) = value.parse() {
+                        scroll_x = scroll;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
                 }
                 _ => {
-                    println!("Unsupported attribute <{}>", attr.name);
+                    errors.push(attr.name, &value, AttributeErrorReason::Unsupported);
                 }
             }
         }
 
+        let (layout_memorizer, diagnostics) = ctx;
+        errors.report(diagnostics);
+
         let changed = (scroll_x != self.scroll_x) || (scroll_y != self.scroll_y);
 
         if changed {
-            ctx.lock().unwrap().mark_as_dirty(node.id());
+            layout_memorizer.lock().unwrap().mark_as_dirty(node.id());
         }
 
         *self = Self {
@@ -6,6 +6,11 @@ use dioxus_native_core::state::ParentDepState;
 use dioxus_native_core_macro::sorted_str_slice;
 use freya_common::LayoutMemorizer;
 
+use crate::{
+    parse_calc, parse_length, AttributeErrorReason, AttributeErrors, CalcType, ParseCalcError,
+    SafeDiagnostics, UnknownKeyword,
+};
+
 #[derive(Default, Clone, Debug)]
 pub struct Size {
     pub width: SizeMode,
@@ -15,12 +20,30 @@ pub struct Size {
     pub max_height: SizeMode,
     pub max_width: SizeMode,
     pub padding: (f32, f32, f32, f32),
+    pub margin: (f32, f32, f32, f32),
     pub direction: DirectionMode,
+    pub wrap: WrapMode,
+    pub main_align: AlignmentMode,
+    pub cross_align: AlignmentMode,
+    pub row_gap: f32,
+    pub column_gap: f32,
+    pub position: PositionMode,
+    pub top: SizeMode,
+    pub right: SizeMode,
+    pub bottom: SizeMode,
+    pub left: SizeMode,
+    pub grid_columns: Vec<SizeMode>,
+    pub grid_rows: Vec<SizeMode>,
+    pub grid_column: Option<usize>,
+    pub grid_row: Option<usize>,
+    pub grid_column_span: usize,
+    pub grid_row_span: usize,
+    pub aspect_ratio: Option<f32>,
     pub id: usize,
 }
 
 impl ParentDepState for Size {
-    type Ctx = Arc<Mutex<LayoutMemorizer>>;
+    type Ctx = (Arc<Mutex<LayoutMemorizer>>, SafeDiagnostics);
     type DepState = Self;
 
     const NODE_MASK: NodeMask =
@@ -32,7 +55,26 @@ impl ParentDepState for Size {
             "max_height",
             "max_width",
             "padding",
+            "margin",
             "direction",
+            "wrap",
+            "main_align",
+            "cross_align",
+            "gap",
+            "row_gap",
+            "column_gap",
+            "position",
+            "top",
+            "right",
+            "bottom",
+            "left",
+            "grid_columns",
+            "grid_rows",
+            "grid_column",
+            "grid_row",
+            "grid_column_span",
+            "grid_row_span",
+            "aspect_ratio",
         ])))
         .with_text()
         .with_tag();
@@ -50,6 +92,7 @@ impl ParentDepState for Size {
         let mut max_height = SizeMode::default();
         let mut max_width = SizeMode::default();
         let mut padding = (0.0, 0.0, 0.0, 0.0);
+        let mut margin = (0.0, 0.0, 0.0, 0.0);
         let mut direction = if let Some("label") = node.tag() {
             DirectionMode::Both
         } else if let Some("paragraph") = node.tag() {
@@ -61,68 +104,191 @@ impl ParentDepState for Size {
         } else {
             DirectionMode::Vertical
         };
+        let mut wrap = WrapMode::default();
+        let mut main_align = AlignmentMode::default();
+        let mut cross_align = AlignmentMode::default();
+        let mut row_gap = 0.0;
+        let mut column_gap = 0.0;
+        let mut position = PositionMode::default();
+        let mut top = SizeMode::default();
+        let mut right = SizeMode::default();
+        let mut bottom = SizeMode::default();
+        let mut left = SizeMode::default();
+        let mut grid_columns = Vec::new();
+        let mut grid_rows = Vec::new();
+        let mut grid_column = None;
+        let mut grid_row = None;
+        let mut grid_column_span = 1;
+        let mut grid_row_span = 1;
+        let mut aspect_ratio = None;
+
+        let mut errors = AttributeErrors::new(&node, "size");
 
         for a in node.attributes() {
+            let value = a.value.to_string();
             match a.name {
-                "width" => {
-                    let attr = a.value.to_string();
-                    if let Some(new_width) = parse_size(&attr) {
-                        width = new_width;
+                "width" => match parse_size(&value) {
+                    Ok(new_width) => width = new_width,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "height" => match parse_size(&value) {
+                    Ok(new_height) => height = new_height,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "min_height" => match parse_size(&value) {
+                    Ok(new_min_height) => min_height = new_min_height,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "min_width" => match parse_size(&value) {
+                    Ok(new_min_width) => min_width = new_min_width,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "max_height" => match parse_size(&value) {
+                    Ok(new_max_height) => max_height = new_max_height,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "max_width" => match parse_size(&value) {
+                    Ok(new_max_width) => max_width = new_max_width,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "padding" => {
+                    if let Some(new_padding) = parse_padding(&value) {
+                        padding = new_padding;
+                    } else {
+                        errors.invalid_value(a.name, &value);
                     }
                 }
-                "height" => {
-                    let attr = a.value.to_string();
-                    if let Some(new_height) = parse_size(&attr) {
-                        height = new_height;
+                "margin" => {
+                    if let Some(new_margin) = parse_measures(&value) {
+                        margin = new_margin;
+                    } else {
+                        errors.invalid_value(a.name, &value);
                     }
                 }
-                "min_height" => {
-                    let attr = a.value.to_string();
-                    if let Some(new_min_height) = parse_size(&attr) {
-                        min_height = new_min_height;
+                "direction" => {
+                    if let Some(new_direction) = parse_direction(&value) {
+                        direction = new_direction;
+                    } else {
+                        errors.invalid_value(a.name, &value);
                     }
                 }
-                "min_width" => {
-                    let attr = a.value.to_string();
-                    if let Some(new_min_width) = parse_size(&attr) {
-                        min_width = new_min_width;
+                "wrap" => {
+                    if let Ok(new_wrap) = try_parse_wrap(&value) {
+                        wrap = new_wrap;
+                    } else {
+                        errors.invalid_value(a.name, &value);
                     }
                 }
-                "max_height" => {
-                    let attr = a.value.to_string();
-                    if let Some(new_max_height) = parse_size(&attr) {
-                        max_height = new_max_height;
+                "main_align" => {
+                    if let Ok(new_main_align) = try_parse_alignment(&value) {
+                        main_align = new_main_align;
+                    } else {
+                        errors.invalid_value(a.name, &value);
                     }
                 }
-                "max_width" => {
-                    let attr = a.value.to_string();
-                    if let Some(new_max_width) = parse_size(&attr) {
-                        max_width = new_max_width;
+                "cross_align" => {
+                    if let Ok(new_cross_align) = try_parse_alignment(&value) {
+                        cross_align = new_cross_align;
+                    } else {
+                        errors.invalid_value(a.name, &value);
                     }
                 }
-                "padding" => {
-                    let total_padding: f32 = a.value.to_string().parse().unwrap();
-                    let padding_for_side = total_padding / 2.0;
-                    padding.0 = padding_for_side;
-                    padding.1 = padding_for_side;
-                    padding.2 = padding_for_side;
-                    padding.3 = padding_for_side;
+                "gap" => {
+                    if let Ok(gap) = value.parse::<f32>() {
+                        row_gap = gap;
+                        column_gap = gap;
+                    } else {
+                        errors.invalid_value(a.name, &value);
+                    }
                 }
-                "direction" => {
-                    direction = if a.value.to_string() == "horizontal" {
-                        DirectionMode::Horizontal
-                    } else if a.value.to_string() == "both" {
-                        DirectionMode::Both
+                "row_gap" => {
+                    if let Ok(gap) = value.parse() {
+                        row_gap = gap;
+                    } else {
+                        errors.invalid_value(a.name, &value);
+                    }
+                }
+                "column_gap" => {
+                    if let Ok(gap) = value.parse() {
+                        column_gap = gap;
+                    } else {
+                        errors.invalid_value(a.name, &value);
+                    }
+                }
+                "position" => {
+                    if let Ok(new_position) = try_parse_position(&value) {
+                        position = new_position;
+                    } else {
+                        errors.invalid_value(a.name, &value);
+                    }
+                }
+                "top" => match parse_size(&value) {
+                    Ok(new_top) => top = new_top,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "right" => match parse_size(&value) {
+                    Ok(new_right) => right = new_right,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "bottom" => match parse_size(&value) {
+                    Ok(new_bottom) => bottom = new_bottom,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "left" => match parse_size(&value) {
+                    Ok(new_left) => left = new_left,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "grid_columns" => match parse_tracks(&value) {
+                    Ok(tracks) => grid_columns = tracks,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "grid_rows" => match parse_tracks(&value) {
+                    Ok(tracks) => grid_rows = tracks,
+                    Err(error) => errors.invalid_calculation(a.name, &value, error),
+                },
+                "grid_column" => {
+                    if let Ok(column) = value.parse() {
+                        grid_column = Some(column);
+                    } else {
+                        errors.invalid_value(a.name, &value);
+                    }
+                }
+                "grid_row" => {
+                    if let Ok(row) = value.parse() {
+                        grid_row = Some(row);
+                    } else {
+                        errors.invalid_value(a.name, &value);
+                    }
+                }
+                "grid_column_span" => {
+                    if let Ok(span) = value.parse::<usize>() {
+                        grid_column_span = span.max(1);
+                    } else {
+                        errors.invalid_value(a.name, &value);
+                    }
+                }
+                "grid_row_span" => {
+                    if let Ok(span) = value.parse::<usize>() {
+                        grid_row_span = span.max(1);
                     } else {
-                        DirectionMode::Vertical
-                    };
+                        errors.invalid_value(a.name, &value);
+                    }
+                }
+                "aspect_ratio" => {
+                    aspect_ratio = parse_aspect_ratio(&value);
+                    if aspect_ratio.is_none() {
+                        errors.invalid_value(a.name, &value);
+                    }
                 }
                 _ => {
-                    println!("Unsupported attribute <{}>", a.name);
+                    errors.push(a.name, &value, AttributeErrorReason::Unsupported);
                 }
             }
         }
 
+        let (layout_memorizer, diagnostics) = ctx;
+        errors.report(diagnostics);
+
         let changed = (width != self.width)
             || (height != self.height)
             || (min_height != self.min_height)
@@ -130,10 +296,28 @@ impl ParentDepState for Size {
             || (max_height != self.max_height)
             || (max_width != self.max_width)
             || (padding != self.padding)
-            || (direction != self.direction);
+            || (margin != self.margin)
+            || (direction != self.direction)
+            || (wrap != self.wrap)
+            || (main_align != self.main_align)
+            || (cross_align != self.cross_align)
+            || (row_gap != self.row_gap)
+            || (column_gap != self.column_gap)
+            || (position != self.position)
+            || (top != self.top)
+            || (right != self.right)
+            || (bottom != self.bottom)
+            || (left != self.left)
+            || (grid_columns != self.grid_columns)
+            || (grid_rows != self.grid_rows)
+            || (grid_column != self.grid_column)
+            || (grid_row != self.grid_row)
+            || (grid_column_span != self.grid_column_span)
+            || (grid_row_span != self.grid_row_span)
+            || (aspect_ratio != self.aspect_ratio);
 
         if changed {
-            ctx.lock().unwrap().mark_as_dirty(node.id());
+            layout_memorizer.lock().unwrap().mark_as_dirty(node.id());
         }
 
         *self = Self {
@@ -144,54 +328,163 @@ impl ParentDepState for Size {
             max_height,
             max_width,
             padding,
+            margin,
             direction,
+            wrap,
+            main_align,
+            cross_align,
+            row_gap,
+            column_gap,
+            position,
+            top,
+            right,
+            bottom,
+            left,
+            grid_columns,
+            grid_rows,
+            grid_column,
+            grid_row,
+            grid_column_span,
+            grid_row_span,
+            aspect_ratio,
             id: node.id().0,
         };
         changed
     }
 }
 
-pub fn parse_size(size: &str) -> Option<SizeMode> {
+/// Parse the size of a node, the error explains why a `calc()` or a length couldn't be parsed
+pub fn parse_size(size: &str) -> Result<SizeMode, ParseCalcError> {
+    let invalid_number = || ParseCalcError::InvalidNumber(size.to_string());
     if size == "stretch" {
-        Some(SizeMode::Percentage(100.0))
+        Ok(SizeMode::Percentage(100.0))
     } else if size == "auto" {
-        Some(SizeMode::Auto)
+        Ok(SizeMode::Auto)
+    } else if size.starts_with("flex(") {
+        Ok(SizeMode::Flex(parse_flex(size).ok_or_else(invalid_number)?))
     } else if size.contains("calc") {
-        Some(SizeMode::Calculation(parse_calc(size)?))
+        Ok(SizeMode::Calculation(parse_calc(size)?))
     } else if size.contains('%') {
-        Some(SizeMode::Percentage(size.replace('%', "").parse().ok()?))
-    } else if size.contains("calc") {
-        Some(SizeMode::Calculation(parse_calc(size)?))
+        Ok(SizeMode::Percentage(
+            size.replace('%', "")
+                .parse()
+                .map_err(|_| invalid_number())?,
+        ))
+    } else if let Ok(size) = size.parse() {
+        Ok(SizeMode::Manual(size))
     } else {
-        Some(SizeMode::Manual(size.parse().ok()?))
+        // Numbers with a unit, e.g `10px`, `50vw` or `2em`
+        match parse_length(size)? {
+            CalcType::Manual(size) => Ok(SizeMode::Manual(size)),
+            length => Ok(SizeMode::Calculation(length)),
+        }
     }
 }
 
-pub fn parse_calc(mut size: &str) -> Option<Vec<CalcType>> {
-    let mut calcs = Vec::new();
-
-    size = size.strip_prefix("calc(")?;
-    size = size.strip_suffix(')')?;
-
-    let vals = size.split_whitespace();
-
-    for val in vals {
-        if val.contains('%') {
-            calcs.push(CalcType::Percentage(val.replace('%', "").parse().ok()?));
-        } else if val == "+" {
-            calcs.push(CalcType::Add);
-        } else if val == "-" {
-            calcs.push(CalcType::Sub);
-        } else if val == "/" {
-            calcs.push(CalcType::Div);
-        } else if val == "*" {
-            calcs.push(CalcType::Mul);
-        } else {
-            calcs.push(CalcType::Manual(val.parse().ok()?));
+/// Parse a ratio between the width and the height, e.g `"16 / 9"` or `"1.5"`
+pub fn parse_aspect_ratio(value: &str) -> Option<f32> {
+    let ratio = match value.split_once('/') {
+        Some((width, height)) => {
+            width.trim().parse::<f32>().ok()? / height.trim().parse::<f32>().ok()?
+        }
+        None => value.trim().parse().ok()?,
+    };
+
+    if ratio.is_finite() && ratio > 0.0 {
+        Some(ratio)
+    } else {
+        None
+    }
+}
+
+/// Parse the comma-separated tracks of a grid, e.g `"100, flex(1), calc(50% - 20)"`.
+/// `auto` tracks take an equal share of the space left by the other tracks, like `flex(1)`.
+pub fn parse_tracks(value: &str) -> Result<Vec<SizeMode>, ParseCalcError> {
+    split_outside_parentheses(value, ',')
+        .map(|track| parse_size(track.trim()))
+        .collect()
+}
+
+/// Split a value by the separators that are not inside of parentheses,
+/// e.g `"calc(50% - 20), 100"` is split by the second comma only.
+pub(crate) fn split_outside_parentheses(
+    value: &str,
+    separator: char,
+) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    value.split(move |c| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        c == separator && depth == 0
+    })
+}
+
+/// Parse CSS-like shorthands for the sides of a node, in the form of
+/// `"all"`, `"vertical horizontal"`, `"top horizontal bottom"` or `"top right bottom left"`.
+pub fn parse_measures(value: &str) -> Option<(f32, f32, f32, f32)> {
+    let values = value
+        .split_whitespace()
+        .map(|v| v.parse::<f32>().ok())
+        .collect::<Option<Vec<f32>>>()?;
+
+    match values[..] {
+        [all] => Some((all, all, all, all)),
+        [vertical, horizontal] => Some((vertical, horizontal, vertical, horizontal)),
+        [top, horizontal, bottom] => Some((top, horizontal, bottom, horizontal)),
+        [top, right, bottom, left] => Some((top, right, bottom, left)),
+        _ => None,
+    }
+}
+
+/// Parse a padding like [`parse_measures`], except that a single value is split
+/// between the opposite sides, e.g `"10"` pads every side by `5`.
+pub fn parse_padding(value: &str) -> Option<(f32, f32, f32, f32)> {
+    let padding = parse_measures(value)?;
+    if value.split_whitespace().count() == 1 {
+        let half = padding.0 / 2.0;
+        Some((half, half, half, half))
+    } else {
+        Some(padding)
+    }
+}
+
+pub fn parse_flex(size: &str) -> Option<f32> {
+    let weight: f32 = size
+        .strip_prefix("flex(")?
+        .strip_suffix(')')?
+        .trim()
+        .parse()
+        .ok()?;
+
+    if weight >= 0.0 {
+        Some(weight)
+    } else {
+        None
+    }
+}
+
+impl Size {
+    /// Space between two consecutive children along the given direction
+    pub fn gap_for(&self, direction: DirectionMode) -> f32 {
+        match direction {
+            DirectionMode::Vertical => self.row_gap,
+            DirectionMode::Horizontal => self.column_gap,
+            DirectionMode::Both => 0.0,
         }
     }
+}
 
-    Some(calcs)
+/// Parse a direction, `None` if the value is not a known one
+pub fn parse_direction(value: &str) -> Option<DirectionMode> {
+    match value {
+        "vertical" => Some(DirectionMode::Vertical),
+        "horizontal" => Some(DirectionMode::Horizontal),
+        "both" => Some(DirectionMode::Both),
+        _ => None,
+    }
 }
 
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
@@ -212,52 +505,122 @@ impl Display for DirectionMode {
     }
 }
 
-#[derive(Default, Clone, Debug, PartialEq)]
-pub enum SizeMode {
+/// Parse a position mode, use `.unwrap_or_default()` to fallback to the default one
+pub fn try_parse_position(value: &str) -> Result<PositionMode, UnknownKeyword> {
+    match value {
+        "static" => Ok(PositionMode::Static),
+        "relative" => Ok(PositionMode::Relative),
+        "absolute" => Ok(PositionMode::Absolute),
+        _ => Err(UnknownKeyword(value.to_string())),
+    }
+}
+
+/// How a node is positioned, `relative` nodes are moved from their place in the flow by
+/// `top`, `right`, `bottom` and `left`, while `absolute` nodes are taken out of the flow
+/// and placed relatively to their nearest positioned ancestor.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PositionMode {
     #[default]
-    Auto,
-    Calculation(Vec<CalcType>),
-    Percentage(f32),
-    Manual(f32),
+    Static,
+    Relative,
+    Absolute,
 }
 
-impl Display for SizeMode {
+impl Display for PositionMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SizeMode::Auto => f.write_str("auto"),
-            SizeMode::Manual(s) => f.write_fmt(format_args!("{s}")),
-            SizeMode::Calculation(calcs) => f.write_fmt(format_args!(
-                "calc({})",
-                calcs
-                    .iter()
-                    .map(|c| c.to_string())
-                    .collect::<Vec<String>>()
-                    .join(" ")
-            )),
-            SizeMode::Percentage(p) => f.write_fmt(format_args!("{p}%")),
+            PositionMode::Static => f.write_str("static"),
+            PositionMode::Relative => f.write_str("relative"),
+            PositionMode::Absolute => f.write_str("absolute"),
+        }
+    }
+}
+
+/// Parse a wrap mode, use `.unwrap_or_default()` to fallback to the default one
+pub fn try_parse_wrap(value: &str) -> Result<WrapMode, UnknownKeyword> {
+    match value {
+        "nowrap" => Ok(WrapMode::NoWrap),
+        "wrap" => Ok(WrapMode::Wrap),
+        _ => Err(UnknownKeyword(value.to_string())),
+    }
+}
+
+/// Whether the children that don't fit in the current line along the direction
+/// of their parent are moved to a new line (or column) or not.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WrapMode {
+    #[default]
+    NoWrap,
+    Wrap,
+}
+
+impl Display for WrapMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WrapMode::NoWrap => f.write_str("nowrap"),
+            WrapMode::Wrap => f.write_str("wrap"),
+        }
+    }
+}
+
+/// Parse an alignment mode, use `.unwrap_or_default()` to fallback to the default one
+pub fn try_parse_alignment(value: &str) -> Result<AlignmentMode, UnknownKeyword> {
+    match value {
+        "start" => Ok(AlignmentMode::Start),
+        "center" => Ok(AlignmentMode::Center),
+        "end" => Ok(AlignmentMode::End),
+        "space-between" => Ok(AlignmentMode::SpaceBetween),
+        "space-around" => Ok(AlignmentMode::SpaceAround),
+        "space-evenly" => Ok(AlignmentMode::SpaceEvenly),
+        _ => Err(UnknownKeyword(value.to_string())),
+    }
+}
+
+/// How the children are distributed along the main axis (`main_align`)
+/// or positioned in the cross axis (`cross_align`) of their parent.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlignmentMode {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Display for AlignmentMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlignmentMode::Start => f.write_str("start"),
+            AlignmentMode::Center => f.write_str("center"),
+            AlignmentMode::End => f.write_str("end"),
+            AlignmentMode::SpaceBetween => f.write_str("space-between"),
+            AlignmentMode::SpaceAround => f.write_str("space-around"),
+            AlignmentMode::SpaceEvenly => f.write_str("space-evenly"),
         }
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub enum CalcType {
-    Sub,
-    Mul,
-    Div,
-    Add,
+#[derive(Default, Clone, Debug, PartialEq)]
+pub enum SizeMode {
+    #[default]
+    Auto,
+    Calculation(CalcType),
     Percentage(f32),
     Manual(f32),
+    /// Take a share of the space left by the siblings, proportional to the given weight
+    Flex(f32),
 }
 
-impl Display for CalcType {
+impl Display for SizeMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CalcType::Sub => f.write_str("-"),
-            CalcType::Mul => f.write_str("*"),
-            CalcType::Div => f.write_str("/"),
-            CalcType::Add => f.write_str("+"),
-            CalcType::Percentage(p) => f.write_fmt(format_args!("{p}%")),
-            CalcType::Manual(s) => f.write_fmt(format_args!("{s}")),
+            SizeMode::Auto => f.write_str("auto"),
+            SizeMode::Manual(s) => f.write_fmt(format_args!("{s}")),
+            SizeMode::Calculation(calc) => f.write_fmt(format_args!("calc({calc})")),
+            SizeMode::Percentage(p) => f.write_fmt(format_args!("{p}%")),
+            SizeMode::Flex(w) => f.write_fmt(format_args!("flex({w})")),
         }
     }
 }
@@ -5,21 +5,43 @@ use dioxus_native_core::state::NodeDepState;
 use dioxus_native_core_macro::sorted_str_slice;
 use skia_safe::Color;
 
-use crate::parse_color;
+use crate::{
+    hash_image_data, parse_blur, parse_color, parse_fill, parse_filter_amount,
+    parse_image_sampling, parse_image_slice, parse_measures, parse_object_fit,
+    parse_object_position, parse_transform, parse_transform_origin, split_outside_parentheses,
+    AttributeErrorReason, AttributeErrors, Fill, Filters, ImageSampling, ImageSlice, ObjectFit,
+    ObjectPosition, SafeDiagnostics, Transform, TransformOrigin, UnknownKeyword,
+};
 
 #[derive(Default, Clone, Debug)]
 pub struct Style {
-    pub background: Color,
+    pub background: Fill,
     pub relative_layer: i16,
-    pub shadow: ShadowSettings,
-    pub radius: f32,
+    pub shadows: Vec<ShadowSettings>,
+    pub radius: CornerRadius,
+    pub border: Border,
+    /// From `0.0` (invisible) to `1.0` (opaque), `None` if not specified
+    pub opacity: Option<f32>,
+    pub transform: Vec<Transform>,
+    pub transform_origin: TransformOrigin,
+    pub filters: Filters,
+    /// Blur of whatever is behind the node, in pixels
+    pub backdrop_blur: f32,
     pub image_data: Option<Vec<u8>>,
+    /// Hash of `image_data`, calculated once when it's set
+    pub image_data_hash: u64,
+    /// File of the image, only used if there is no `image_data`
+    pub image_path: Option<String>,
+    pub object_fit: ObjectFit,
+    pub object_position: ObjectPosition,
+    pub sampling: ImageSampling,
+    pub image_slice: Option<ImageSlice>,
     pub svg_data: Option<Vec<u8>>,
     pub display: DisplayMode,
 }
 
 impl NodeDepState<()> for Style {
-    type Ctx = ();
+    type Ctx = SafeDiagnostics;
 
     const NODE_MASK: NodeMask =
         NodeMask::new_with_attrs(AttributeMask::Static(&sorted_str_slice!([
@@ -27,80 +49,332 @@ impl NodeDepState<()> for Style {
             "layer",
             "shadow",
             "radius",
+            "border",
+            "border_align",
+            "opacity",
+            "transform",
+            "transform_origin",
+            "blur",
+            "backdrop_blur",
+            "brightness",
+            "saturate",
+            "grayscale",
             "image_data",
+            "image_path",
+            "object_fit",
+            "object_position",
+            "sampling",
+            "slice",
             "svg_data",
             "svg_content",
             "display",
-        ])));
+        ])))
+        .with_tag();
 
-    fn reduce<'a>(&mut self, node: NodeView, _sibling: (), _ctx: &Self::Ctx) -> bool {
-        let mut background = Color::TRANSPARENT;
+    fn reduce<'a>(&mut self, node: NodeView, _sibling: (), ctx: &Self::Ctx) -> bool {
+        let mut background = Fill::default();
         let mut relative_layer = 0;
-        let mut shadow = ShadowSettings::default();
-        let mut radius = 0.0;
+        let mut shadows = Vec::new();
+        let mut radius = CornerRadius::default();
+        let mut border = Border::default();
+        let mut border_align = None;
+        let mut opacity = None;
+        let mut transform = Vec::new();
+        let mut transform_origin = TransformOrigin::default();
+        let mut filters = Filters::default();
+        let mut backdrop_blur = 0.0;
         let mut image_data = None;
+        let mut image_data_hash = 0;
+        let mut image_path = None;
+        let mut object_fit = ObjectFit::default();
+        let mut object_position = ObjectPosition::default();
+        let mut sampling = ImageSampling::default();
+        let mut image_slice = None;
         let mut svg_data = None;
         let mut display = DisplayMode::Normal;
 
+        let mut errors = AttributeErrors::new(&node, "style");
+
         for attr in node.attributes() {
             match attr.name {
-                "display" => display = parse_display(&attr.value.to_string()),
+                "display" => {
+                    let value = attr.value.to_string();
+                    if let Ok(new_display) = try_parse_display(&value) {
+                        display = new_display;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
                 "background" => {
-                    let new_back = parse_color(&attr.value.to_string());
+                    let value = attr.value.to_string();
+                    let new_back = parse_fill(&value);
                     if let Some(new_back) = new_back {
                         background = new_back;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "layer" => {
-                    let new_relative_layer: Option<i16> = attr.value.to_string().parse().ok();
+                    let value = attr.value.to_string();
+                    let new_relative_layer: Option<i16> = value.parse().ok();
                     if let Some(new_relative_layer) = new_relative_layer {
                         relative_layer = new_relative_layer;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "shadow" => {
-                    let new_shadow = parse_shadow(&attr.value.to_string());
+                    let value = attr.value.to_string();
+                    let new_shadows = parse_shadows(&value);
 
-                    if let Some(new_shadow) = new_shadow {
-                        shadow = new_shadow;
+                    if let Some(new_shadows) = new_shadows {
+                        shadows = new_shadows;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "radius" => {
-                    let new_radius: Option<f32> = attr.value.to_string().parse().ok();
+                    let value = attr.value.to_string();
+                    let new_radius = parse_radius(&value);
 
                     if let Some(new_radius) = new_radius {
                         radius = new_radius;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "border" => {
+                    let value = attr.value.to_string();
+                    let new_border = parse_border(&value);
+
+                    if let Some(new_border) = new_border {
+                        border = new_border;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "border_align" => {
+                    let value = attr.value.to_string();
+                    let new_border_align = parse_border_align(&value);
+
+                    if new_border_align.is_some() {
+                        border_align = new_border_align;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "opacity" => {
+                    let value = attr.value.to_string();
+                    let new_opacity = parse_opacity(&value);
+
+                    if new_opacity.is_some() {
+                        opacity = new_opacity;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "transform" => {
+                    let value = attr.value.to_string();
+                    let new_transform = parse_transform(&value);
+
+                    if let Some(new_transform) = new_transform {
+                        transform = new_transform;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "transform_origin" => {
+                    let value = attr.value.to_string();
+                    let new_transform_origin = parse_transform_origin(&value);
+
+                    if let Some(new_transform_origin) = new_transform_origin {
+                        transform_origin = new_transform_origin;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "blur" => {
+                    let value = attr.value.to_string();
+                    let new_blur = parse_blur(&value);
+
+                    if let Some(new_blur) = new_blur {
+                        filters.blur = new_blur;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "backdrop_blur" => {
+                    let value = attr.value.to_string();
+                    let new_backdrop_blur = parse_blur(&value);
+
+                    if let Some(new_backdrop_blur) = new_backdrop_blur {
+                        backdrop_blur = new_backdrop_blur;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "brightness" => {
+                    let value = attr.value.to_string();
+                    let new_brightness = parse_filter_amount(&value);
+
+                    if let Some(new_brightness) = new_brightness {
+                        filters.brightness = new_brightness;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "saturate" => {
+                    let value = attr.value.to_string();
+                    let new_saturate = parse_filter_amount(&value);
+
+                    if let Some(new_saturate) = new_saturate {
+                        filters.saturate = new_saturate;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "grayscale" => {
+                    let value = attr.value.to_string();
+                    let new_grayscale = parse_filter_amount(&value);
+
+                    if let Some(new_grayscale) = new_grayscale {
+                        // Like in CSS, anything past fully gray is just fully gray
+                        filters.grayscale = new_grayscale.min(1.0);
+                    } else {
+                        errors.invalid_value(attr.name, &value);
                     }
                 }
                 "image_data" => {
                     let bytes = attr.value.as_bytes();
                     image_data = bytes.map(|v| v.to_vec());
+                    if let Some(bytes) = bytes {
+                        image_data_hash = hash_image_data(bytes);
+                    } else {
+                        errors.push(
+                            attr.name,
+                            &attr.value.to_string(),
+                            AttributeErrorReason::UnexpectedType,
+                        );
+                    }
+                }
+                "image_path" => {
+                    image_path = Some(attr.value.to_string());
+                }
+                "object_fit" => {
+                    let value = attr.value.to_string();
+                    let new_object_fit = parse_object_fit(&value);
+
+                    if let Some(new_object_fit) = new_object_fit {
+                        object_fit = new_object_fit;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "object_position" => {
+                    let value = attr.value.to_string();
+                    let new_object_position = parse_object_position(&value);
+
+                    if let Some(new_object_position) = new_object_position {
+                        object_position = new_object_position;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "sampling" => {
+                    let value = attr.value.to_string();
+                    let new_sampling = parse_image_sampling(&value);
+
+                    if let Some(new_sampling) = new_sampling {
+                        sampling = new_sampling;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
+                }
+                "slice" => {
+                    let value = attr.value.to_string();
+                    let new_image_slice = parse_image_slice(&value);
+
+                    if new_image_slice.is_some() {
+                        image_slice = new_image_slice;
+                    } else {
+                        errors.invalid_value(attr.name, &value);
+                    }
                 }
                 "svg_data" => {
                     let bytes = attr.value.as_bytes();
                     svg_data = bytes.map(|v| v.to_vec());
+                    if svg_data.is_none() {
+                        errors.push(
+                            attr.name,
+                            &attr.value.to_string(),
+                            AttributeErrorReason::UnexpectedType,
+                        );
+                    }
                 }
                 "svg_content" => {
                     let text = attr.value.as_text();
                     svg_data = text.map(|v| v.as_bytes().to_vec());
+                    if svg_data.is_none() {
+                        errors.push(
+                            attr.name,
+                            &attr.value.to_string(),
+                            AttributeErrorReason::UnexpectedType,
+                        );
+                    }
                 }
                 _ => {
-                    println!("Unsupported attribute <{}>", attr.name);
+                    errors.push(
+                        attr.name,
+                        &attr.value.to_string(),
+                        AttributeErrorReason::Unsupported,
+                    );
                 }
             }
         }
 
+        errors.report(ctx);
+
+        if let Some(border_align) = border_align {
+            border.alignment = border_align;
+        }
+
         let changed = (background != self.background)
             || (relative_layer != self.relative_layer)
-            || (shadow != self.shadow)
+            || (shadows != self.shadows)
             || (radius != self.radius)
-            || (image_data != self.image_data);
+            || (border != self.border)
+            || (opacity != self.opacity)
+            || (transform != self.transform)
+            || (transform_origin != self.transform_origin)
+            || (filters != self.filters)
+            || (backdrop_blur != self.backdrop_blur)
+            || (image_data != self.image_data)
+            || (image_path != self.image_path)
+            || (object_fit != self.object_fit)
+            || (object_position != self.object_position)
+            || (sampling != self.sampling)
+            || (image_slice != self.image_slice)
+            || (svg_data != self.svg_data)
+            || (display != self.display);
 
         *self = Self {
             background,
             relative_layer,
-            shadow,
+            shadows,
             radius,
+            border,
+            opacity,
+            transform,
+            transform_origin,
+            filters,
+            backdrop_blur,
             image_data,
+            image_data_hash,
+            image_path,
+            object_fit,
+            object_position,
+            sampling,
+            image_slice,
             svg_data,
             display,
         };
@@ -108,22 +382,127 @@ impl NodeDepState<()> for Style {
     }
 }
 
+/// Parse a comma-separated list of shadows, e.g `"0 2 60 4 black, inset 0 0 30 8 2 white"`, or `"none"`.
+pub fn parse_shadows(value: &str) -> Option<Vec<ShadowSettings>> {
+    if value.trim() == "none" {
+        return Some(Vec::new());
+    }
+
+    split_outside_parentheses(value, ',')
+        .map(parse_shadow)
+        .collect()
+}
+
+/// Parse a shadow in the form of `"[inset] x y intensity blur [spread] color"`.
 pub fn parse_shadow(value: &str) -> Option<ShadowSettings> {
-    let value = value.to_string();
-    let mut shadow_values = value.split_ascii_whitespace();
+    let mut shadow_values = split_outside_parentheses(value, ' ').filter(|value| !value.is_empty());
+
+    let mut first = shadow_values.next()?;
+    let inset = first == "inset";
+    if inset {
+        first = shadow_values.next()?;
+    }
+
+    let x = first.parse().ok()?;
+    let y = shadow_values.next()?.parse().ok()?;
+    let intensity = shadow_values.next()?.parse().ok()?;
+    let blur = shadow_values.next()?.parse().ok()?;
+
+    // The spread is optional, so the next value is either the spread or the color
+    let next = shadow_values.next()?;
+    let (spread, color) = match next.parse() {
+        Ok(spread) => (spread, shadow_values.next()?),
+        Err(_) => (0.0, next),
+    };
+    let color = parse_color(color)?;
+
+    if shadow_values.next().is_some() {
+        return None;
+    }
+
     Some(ShadowSettings {
-        x: shadow_values.next()?.parse().ok()?,
-        y: shadow_values.next()?.parse().ok()?,
-        intensity: shadow_values.next()?.parse().ok()?,
-        size: shadow_values.next()?.parse().ok()?,
-        color: parse_color(shadow_values.next()?)?,
+        x,
+        y,
+        intensity,
+        blur,
+        spread,
+        color,
+        inset,
+    })
+}
+
+/// Parse the radius of the corners, e.g `"8"` or `"8 8 0 0"`. Like in CSS, corners go
+/// clockwise from the top left one, and missing corners take the value of the opposite one.
+pub fn parse_radius(value: &str) -> Option<CornerRadius> {
+    // The shorthands of the corners map just like the ones of the sides
+    let (top_left, top_right, bottom_right, bottom_left) = parse_measures(value)?;
+    Some(CornerRadius {
+        top_left,
+        top_right,
+        bottom_right,
+        bottom_left,
+    })
+}
+
+/// Parse a border in the form of `"width style color"`, e.g `"2 dashed red"`, or `"none"`.
+pub fn parse_border(value: &str) -> Option<Border> {
+    if value.trim() == "none" {
+        return Some(Border::default());
+    }
+
+    let mut values = split_outside_parentheses(value, ' ').filter(|v| !v.is_empty());
+
+    let width = values.next()?.parse::<f32>().ok()?;
+    let style = match values.next()? {
+        "none" => BorderStyle::None,
+        "solid" => BorderStyle::Solid,
+        "dashed" => BorderStyle::Dashed,
+        "dotted" => BorderStyle::Dotted,
+        _ => return None,
+    };
+    let color = parse_color(values.next()?)?;
+
+    if values.next().is_some() || !width.is_finite() || width < 0.0 {
+        return None;
+    }
+
+    Some(Border {
+        width,
+        style,
+        color,
+        alignment: BorderAlignment::default(),
     })
 }
 
-pub fn parse_display(value: &str) -> DisplayMode {
+pub fn parse_border_align(value: &str) -> Option<BorderAlignment> {
+    match value {
+        "inner" => Some(BorderAlignment::Inner),
+        "outer" => Some(BorderAlignment::Outer),
+        "center" => Some(BorderAlignment::Center),
+        _ => None,
+    }
+}
+
+/// Parse an opacity from `0` to `1`, or from `0%` to `100%`. Values out of range are clamped.
+pub fn parse_opacity(value: &str) -> Option<f32> {
+    let opacity = match value.trim().strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f32>().ok()? / 100.0,
+        None => value.trim().parse::<f32>().ok()?,
+    };
+    if opacity.is_nan() {
+        None
+    } else {
+        Some(opacity.clamp(0.0, 1.0))
+    }
+}
+
+/// Parse a display mode, use `.unwrap_or_default()` to fallback to the default one
+pub fn try_parse_display(value: &str) -> Result<DisplayMode, UnknownKeyword> {
     match value {
-        "center" => DisplayMode::Center,
-        _ => DisplayMode::Normal,
+        "normal" => Ok(DisplayMode::Normal),
+        "center" => Ok(DisplayMode::Center),
+        "grid" => Ok(DisplayMode::Grid),
+        _ => Err(UnknownKeyword(value.to_string())),
     }
 }
 
@@ -132,6 +511,7 @@ pub enum DisplayMode {
     #[default]
     Normal,
     Center,
+    Grid,
 }
 
 impl Display for DisplayMode {
@@ -139,15 +519,96 @@ impl Display for DisplayMode {
         match self {
             DisplayMode::Normal => f.write_str("normal"),
             DisplayMode::Center => f.write_str("center"),
+            DisplayMode::Grid => f.write_str("grid"),
         }
     }
 }
 
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct ShadowSettings {
+    /// Horizontal offset of the shadow, positive values move it to the right
     pub x: f32,
+    /// Vertical offset of the shadow, positive values move it down
     pub y: f32,
     pub intensity: u8,
-    pub size: f32,
+    pub blur: f32,
+    /// How much the shadow grows (or shrinks if negative) before it's blurred
+    pub spread: f32,
+    pub color: Color,
+    /// Drawn inside of the node instead of under it
+    pub inset: bool,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct CornerRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadius {
+    /// The radius of every corner, starting from the top left one and going clockwise
+    pub fn as_tuple(&self) -> (f32, f32, f32, f32) {
+        (
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        )
+    }
+}
+
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Border {
+    pub width: f32,
+    pub style: BorderStyle,
     pub color: Color,
+    pub alignment: BorderAlignment,
+}
+
+impl Border {
+    /// Whether this border would paint anything
+    pub fn is_visible(&self) -> bool {
+        self.width > 0.0 && self.style != BorderStyle::None && self.color.a() > 0
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BorderStyle {
+    #[default]
+    None,
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl Display for BorderStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BorderStyle::None => f.write_str("none"),
+            BorderStyle::Solid => f.write_str("solid"),
+            BorderStyle::Dashed => f.write_str("dashed"),
+            BorderStyle::Dotted => f.write_str("dotted"),
+        }
+    }
+}
+
+/// Where the border is drawn relative to the edges of the node
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BorderAlignment {
+    #[default]
+    Inner,
+    Outer,
+    Center,
+}
+
+impl Display for BorderAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BorderAlignment::Inner => f.write_str("inner"),
+            BorderAlignment::Outer => f.write_str("outer"),
+            BorderAlignment::Center => f.write_str("center"),
+        }
+    }
 }
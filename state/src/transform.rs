@@ -0,0 +1,158 @@
+use std::fmt::Display;
+
+use crate::{parse_angle, split_outside_parentheses};
+
+/// A transformation applied to a node and its descendants, without affecting the layout
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transform {
+    Translate(f32, f32),
+    Scale(f32, f32),
+    /// Clockwise rotation in degrees
+    Rotate(f32),
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transform::Translate(x, y) => f.write_fmt(format_args!("translate({x}, {y})")),
+            Transform::Scale(x, y) => f.write_fmt(format_args!("scale({x}, {y})")),
+            Transform::Rotate(angle) => f.write_fmt(format_args!("rotate({angle}deg)")),
+        }
+    }
+}
+
+/// Offset of the transform origin from the top left corner of a node
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OriginOffset {
+    Pixels(f32),
+    Percentage(f32),
+}
+
+impl OriginOffset {
+    pub fn resolve(&self, size: f32) -> f32 {
+        match self {
+            OriginOffset::Pixels(pixels) => *pixels,
+            OriginOffset::Percentage(percentage) => size * percentage / 100.0,
+        }
+    }
+}
+
+impl Display for OriginOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OriginOffset::Pixels(pixels) => f.write_fmt(format_args!("{pixels}")),
+            OriginOffset::Percentage(percentage) => f.write_fmt(format_args!("{percentage}%")),
+        }
+    }
+}
+
+/// Point around which a node is scaled and rotated, the center by default
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransformOrigin {
+    pub x: OriginOffset,
+    pub y: OriginOffset,
+}
+
+impl Default for TransformOrigin {
+    fn default() -> Self {
+        Self {
+            x: OriginOffset::Percentage(50.0),
+            y: OriginOffset::Percentage(50.0),
+        }
+    }
+}
+
+impl Display for TransformOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} {}", self.x, self.y))
+    }
+}
+
+/// Parse a list of transformations separated by spaces, applied from left to right,
+/// e.g `"translate(10, 0) rotate(45deg) scale(1.5)"`, or `"none"`.
+pub fn parse_transform(value: &str) -> Option<Vec<Transform>> {
+    if value.trim() == "none" {
+        return Some(Vec::new());
+    }
+
+    split_outside_parentheses(value, ' ')
+        .filter(|function| !function.is_empty())
+        .map(|function| {
+            let (name, args) = function.strip_suffix(')')?.split_once('(')?;
+            let args = args.split(',').map(|arg| arg.trim()).collect::<Vec<&str>>();
+
+            match (name, &args[..]) {
+                ("translate", [x]) => Some(Transform::Translate(parse_pixels(x)?, 0.0)),
+                ("translate", [x, y]) => {
+                    Some(Transform::Translate(parse_pixels(x)?, parse_pixels(y)?))
+                }
+                ("translateX", [x]) => Some(Transform::Translate(parse_pixels(x)?, 0.0)),
+                ("translateY", [y]) => Some(Transform::Translate(0.0, parse_pixels(y)?)),
+                ("scale", [scale]) => {
+                    let scale = parse_number(scale)?;
+                    Some(Transform::Scale(scale, scale))
+                }
+                ("scale", [x, y]) => Some(Transform::Scale(parse_number(x)?, parse_number(y)?)),
+                ("scaleX", [x]) => Some(Transform::Scale(parse_number(x)?, 1.0)),
+                ("scaleY", [y]) => Some(Transform::Scale(1.0, parse_number(y)?)),
+                ("rotate", [angle]) => Some(Transform::Rotate(parse_angle(angle)?)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parse the origin of the transformations, in pixels, percentages or keywords,
+/// e.g `"center"`, `"top left"` or `"25% 10"`.
+pub fn parse_transform_origin(value: &str) -> Option<TransformOrigin> {
+    let (x, y) = parse_position(value)?;
+    Some(TransformOrigin { x, y })
+}
+
+/// Parse a point inside of a node, in pixels, percentages or keywords, e.g `"top left"`.
+pub(crate) fn parse_position(value: &str) -> Option<(OriginOffset, OriginOffset)> {
+    let values = value.split_whitespace().collect::<Vec<&str>>();
+
+    let (x, y) = match values[..] {
+        // Vertical keywords can go first, like in `top left`
+        [y @ ("top" | "bottom"), x] | [x, y] if x != "top" && x != "bottom" => (x, y),
+        [value @ ("top" | "bottom")] => ("center", value),
+        [value] => (value, "center"),
+        _ => return None,
+    };
+
+    let x = match x {
+        "left" => OriginOffset::Percentage(0.0),
+        "center" => OriginOffset::Percentage(50.0),
+        "right" => OriginOffset::Percentage(100.0),
+        x => parse_origin_offset(x)?,
+    };
+    let y = match y {
+        "top" => OriginOffset::Percentage(0.0),
+        "center" => OriginOffset::Percentage(50.0),
+        "bottom" => OriginOffset::Percentage(100.0),
+        y => parse_origin_offset(y)?,
+    };
+
+    Some((x, y))
+}
+
+fn parse_origin_offset(value: &str) -> Option<OriginOffset> {
+    match value.strip_suffix('%') {
+        Some(percentage) => Some(OriginOffset::Percentage(parse_number(percentage)?)),
+        None => Some(OriginOffset::Pixels(parse_pixels(value)?)),
+    }
+}
+
+fn parse_pixels(value: &str) -> Option<f32> {
+    parse_number(value.strip_suffix("px").unwrap_or(value))
+}
+
+fn parse_number(value: &str) -> Option<f32> {
+    let number = value.trim().parse::<f32>().ok()?;
+    if number.is_finite() {
+        Some(number)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,46 @@
+use freya_node_state::{try_parse_alignment, AlignmentMode, UnknownKeyword};
+
+#[test]
+fn parse_center_alignment() {
+    let center = try_parse_alignment("center");
+    assert_eq!(center, Ok(AlignmentMode::Center));
+}
+
+#[test]
+fn parse_end_alignment() {
+    let end = try_parse_alignment("end");
+    assert_eq!(end, Ok(AlignmentMode::End));
+}
+
+#[test]
+fn parse_space_between_alignment() {
+    let space_between = try_parse_alignment("space-between");
+    assert_eq!(space_between, Ok(AlignmentMode::SpaceBetween));
+}
+
+#[test]
+fn parse_space_around_alignment() {
+    let space_around = try_parse_alignment("space-around");
+    assert_eq!(space_around, Ok(AlignmentMode::SpaceAround));
+}
+
+#[test]
+fn parse_space_evenly_alignment() {
+    let space_evenly = try_parse_alignment("space-evenly");
+    assert_eq!(space_evenly, Ok(AlignmentMode::SpaceEvenly));
+}
+
+#[test]
+fn parse_fallback_alignment() {
+    let start = try_parse_alignment("freya!!").unwrap_or_default();
+    assert_eq!(start, AlignmentMode::Start);
+}
+
+#[test]
+fn try_parse_unknown_alignment() {
+    assert_eq!(try_parse_alignment("start"), Ok(AlignmentMode::Start));
+    assert_eq!(
+        try_parse_alignment("freya!!"),
+        Err(UnknownKeyword("freya!!".to_string()))
+    );
+}
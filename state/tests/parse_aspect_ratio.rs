@@ -0,0 +1,19 @@
+use freya_node_state::parse_aspect_ratio;
+
+#[test]
+fn parse_number_aspect_ratio() {
+    let ratio = parse_aspect_ratio("1.5");
+    assert_eq!(ratio, Some(1.5));
+}
+
+#[test]
+fn parse_fraction_aspect_ratio() {
+    let ratio = parse_aspect_ratio("16 / 8");
+    assert_eq!(ratio, Some(2.0));
+}
+
+#[test]
+fn parse_invalid_aspect_ratio() {
+    let ratio = parse_aspect_ratio("16 / 0");
+    assert_eq!(ratio, None);
+}
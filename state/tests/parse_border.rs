@@ -0,0 +1,59 @@
+use freya_node_state::{parse_border, parse_border_align, Border, BorderAlignment, BorderStyle};
+use skia_safe::Color;
+
+#[test]
+fn parse_basic_border() {
+    let border = parse_border("2 solid red");
+    assert_eq!(
+        border,
+        Some(Border {
+            width: 2.0,
+            style: BorderStyle::Solid,
+            color: Color::RED,
+            alignment: BorderAlignment::Inner
+        })
+    );
+}
+
+#[test]
+fn parse_border_with_spaced_color() {
+    let border = parse_border("1.5 dashed rgb(0, 0, 255)");
+    assert_eq!(
+        border,
+        Some(Border {
+            width: 1.5,
+            style: BorderStyle::Dashed,
+            color: Color::BLUE,
+            alignment: BorderAlignment::Inner
+        })
+    );
+}
+
+#[test]
+fn parse_dotted_border() {
+    let border = parse_border("3 dotted #000");
+    assert_eq!(border.map(|border| border.style), Some(BorderStyle::Dotted));
+}
+
+#[test]
+fn parse_none_border() {
+    let border = parse_border("none");
+    assert_eq!(border, Some(Border::default()));
+    assert!(!border.unwrap().is_visible());
+}
+
+#[test]
+fn parse_invalid_border() {
+    assert_eq!(parse_border("2 wavy red"), None);
+    assert_eq!(parse_border("-2 solid red"), None);
+    assert_eq!(parse_border("2 solid"), None);
+    assert_eq!(parse_border("2 solid red blue"), None);
+}
+
+#[test]
+fn parse_border_alignment() {
+    assert_eq!(parse_border_align("inner"), Some(BorderAlignment::Inner));
+    assert_eq!(parse_border_align("outer"), Some(BorderAlignment::Outer));
+    assert_eq!(parse_border_align("center"), Some(BorderAlignment::Center));
+    assert_eq!(parse_border_align("middle"), None);
+}
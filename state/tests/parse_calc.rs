@@ -0,0 +1,103 @@
+use freya_node_state::{parse_calc, CalcType, ParseCalcError};
+
+#[test]
+fn parse_calc_precedence() {
+    let calc = parse_calc("calc(100% - 20 * 2)");
+    assert_eq!(
+        calc,
+        Ok(CalcType::Sub(
+            Box::new(CalcType::Percentage(100.0)),
+            Box::new(CalcType::Mul(
+                Box::new(CalcType::Manual(20.0)),
+                Box::new(CalcType::Manual(2.0))
+            ))
+        ))
+    );
+}
+
+#[test]
+fn parse_calc_parentheses() {
+    let calc = parse_calc("calc((100%-20)/3)");
+    assert_eq!(
+        calc,
+        Ok(CalcType::Div(
+            Box::new(CalcType::Sub(
+                Box::new(CalcType::Percentage(100.0)),
+                Box::new(CalcType::Manual(20.0))
+            )),
+            Box::new(CalcType::Manual(3.0))
+        ))
+    );
+}
+
+#[test]
+fn parse_calc_unary_minus() {
+    let calc = parse_calc("calc(-10 + 5)");
+    assert_eq!(
+        calc,
+        Ok(CalcType::Add(
+            Box::new(CalcType::Neg(Box::new(CalcType::Manual(10.0)))),
+            Box::new(CalcType::Manual(5.0))
+        ))
+    );
+}
+
+#[test]
+fn parse_calc_functions() {
+    let calc = parse_calc("calc(clamp(10, min(50%, 200), max(300, 20)))");
+    assert_eq!(
+        calc,
+        Ok(CalcType::Clamp(
+            Box::new(CalcType::Manual(10.0)),
+            Box::new(CalcType::Min(vec![
+                CalcType::Percentage(50.0),
+                CalcType::Manual(200.0)
+            ])),
+            Box::new(CalcType::Max(vec![
+                CalcType::Manual(300.0),
+                CalcType::Manual(20.0)
+            ]))
+        ))
+    );
+}
+
+#[test]
+fn display_calc() {
+    let calc = parse_calc("calc((100% - 20) / 3 - -(5 - 2))").unwrap();
+    assert_eq!(calc.to_string(), "(100% - 20) / 3 - -(5 - 2)");
+}
+
+#[test]
+fn parse_calc_errors() {
+    assert_eq!(parse_calc("100 - 20"), Err(ParseCalcError::NotACalculation));
+    assert_eq!(
+        parse_calc("calc(100 - )"),
+        Err(ParseCalcError::UnexpectedToken {
+            position: 11,
+            token: ")".to_string()
+        })
+    );
+    assert_eq!(
+        parse_calc("calc((100 - 20)"),
+        Err(ParseCalcError::UnexpectedEnd)
+    );
+    assert_eq!(
+        parse_calc("calc(foo(10))"),
+        Err(ParseCalcError::UnknownFunction("foo".to_string()))
+    );
+    assert_eq!(
+        parse_calc("calc(clamp(10, 20))"),
+        Err(ParseCalcError::WrongArgumentsCount {
+            function: "clamp".to_string(),
+            found: 2
+        })
+    );
+    assert_eq!(
+        parse_calc("calc(10pt)"),
+        Err(ParseCalcError::UnknownUnit("pt".to_string()))
+    );
+    assert_eq!(
+        parse_calc("calc(1.2.3)"),
+        Err(ParseCalcError::InvalidNumber("1.2.3".to_string()))
+    );
+}
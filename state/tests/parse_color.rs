@@ -12,3 +12,103 @@ fn parse_rgb_color() {
     let color = parse_color("rgb(91, 123, 57");
     assert_eq!(color, Some(Color::from_rgb(91, 123, 57)));
 }
+
+#[test]
+fn parse_named_colors() {
+    assert_eq!(parse_color("green"), Some(Color::from_rgb(0, 128, 0)));
+    assert_eq!(
+        parse_color("rebeccapurple"),
+        Some(Color::from_rgb(102, 51, 153))
+    );
+    assert_eq!(
+        parse_color("LightGoldenrodYellow"),
+        Some(Color::from_rgb(250, 250, 210))
+    );
+    assert_eq!(parse_color("transparent"), Some(Color::TRANSPARENT));
+    assert_eq!(parse_color("notacolor"), None);
+}
+
+#[test]
+fn parse_inherit_color() {
+    let color = parse_color("inherit");
+    assert_eq!(color, None);
+}
+
+#[test]
+fn parse_short_hex_color() {
+    let color = parse_color("#f0a");
+    assert_eq!(color, Some(Color::from_rgb(255, 0, 170)));
+}
+
+#[test]
+fn parse_short_hex_color_with_alpha() {
+    let color = parse_color("#f0a8");
+    assert_eq!(color, Some(Color::from_argb(136, 255, 0, 170)));
+}
+
+#[test]
+fn parse_hex_color() {
+    let color = parse_color("#5B7B39");
+    assert_eq!(color, Some(Color::from_rgb(91, 123, 57)));
+}
+
+#[test]
+fn parse_hex_color_with_alpha() {
+    let color = parse_color("#5b7b3980");
+    assert_eq!(color, Some(Color::from_argb(128, 91, 123, 57)));
+}
+
+#[test]
+fn parse_invalid_hex_color() {
+    assert_eq!(parse_color("#5b7b3"), None);
+    assert_eq!(parse_color("#5g7b39"), None);
+    assert_eq!(parse_color("#+5b7b3"), None);
+}
+
+#[test]
+fn parse_rgb_color_with_alpha() {
+    let color = parse_color("rgb(91, 123, 57, 128)");
+    assert_eq!(color, Some(Color::from_argb(128, 91, 123, 57)));
+}
+
+#[test]
+fn parse_rgba_color() {
+    let color = parse_color("rgba(91, 123, 57, 0.5)");
+    assert_eq!(color, Some(Color::from_argb(128, 91, 123, 57)));
+}
+
+#[test]
+fn parse_rgba_color_with_percentage_alpha() {
+    let color = parse_color("rgba(91 123 57 / 25%)");
+    assert_eq!(color, Some(Color::from_argb(64, 91, 123, 57)));
+}
+
+#[test]
+fn parse_rgba_opaque_color() {
+    let color = parse_color("rgba(91, 123, 57, 1)");
+    assert_eq!(color, Some(Color::from_rgb(91, 123, 57)));
+}
+
+#[test]
+fn parse_hsl_color() {
+    let color = parse_color("hsl(120, 100%, 25%)");
+    assert_eq!(color, Some(Color::from_rgb(0, 128, 0)));
+}
+
+#[test]
+fn parse_hsl_color_with_degrees() {
+    let color = parse_color("hsl(-120deg, 100%, 50%)");
+    assert_eq!(color, Some(Color::from_rgb(0, 0, 255)));
+}
+
+#[test]
+fn parse_hsla_color() {
+    let color = parse_color("hsla(0, 100%, 50%, 0.5)");
+    assert_eq!(color, Some(Color::from_argb(128, 255, 0, 0)));
+}
+
+#[test]
+fn parse_invalid_hsl_color() {
+    let color = parse_color("hsl(0, 100, 50)");
+    assert_eq!(color, None);
+}
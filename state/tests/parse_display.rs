@@ -1,19 +1,25 @@
-use freya_node_state::{parse_display, DisplayMode};
+use freya_node_state::{try_parse_display, DisplayMode};
 
 #[test]
 fn parse_normal_display() {
-    let display = parse_display("normal");
-    assert_eq!(display, DisplayMode::Normal);
+    let display = try_parse_display("normal");
+    assert_eq!(display, Ok(DisplayMode::Normal));
 }
 
 #[test]
 fn parse_center_display() {
-    let display = parse_display("center");
-    assert_eq!(display, DisplayMode::Center);
+    let display = try_parse_display("center");
+    assert_eq!(display, Ok(DisplayMode::Center));
+}
+
+#[test]
+fn parse_grid_display() {
+    let display = try_parse_display("grid");
+    assert_eq!(display, Ok(DisplayMode::Grid));
 }
 
 #[test]
 fn parse_fallback_display() {
-    let display = parse_display("freya!!");
+    let display = try_parse_display("freya!!").unwrap_or_default();
     assert_eq!(display, DisplayMode::Normal);
 }
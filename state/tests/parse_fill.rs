@@ -0,0 +1,135 @@
+use freya_node_state::{
+    parse_fill, ConicGradient, Fill, GradientStop, LinearGradient, RadialGradient,
+};
+use skia_safe::Color;
+
+#[test]
+fn parse_color_fill() {
+    let fill = parse_fill("red");
+    assert_eq!(fill, Some(Fill::Color(Color::RED)));
+}
+
+#[test]
+fn parse_linear_gradient() {
+    let fill = parse_fill("linear-gradient(red, rgb(0, 0, 255))");
+    assert_eq!(
+        fill,
+        Some(Fill::LinearGradient(LinearGradient {
+            angle: 180.0,
+            stops: vec![
+                GradientStop {
+                    color: Color::RED,
+                    offset: 0.0
+                },
+                GradientStop {
+                    color: Color::BLUE,
+                    offset: 1.0
+                }
+            ]
+        }))
+    );
+}
+
+#[test]
+fn parse_linear_gradient_direction() {
+    let angle = |value| match parse_fill(value) {
+        Some(Fill::LinearGradient(gradient)) => Some(gradient.angle),
+        _ => None,
+    };
+    assert_eq!(angle("linear-gradient(to right, red, blue)"), Some(90.0));
+    assert_eq!(angle("linear-gradient(to left, red, blue)"), Some(270.0));
+    assert_eq!(
+        angle("linear-gradient(to top right, red, blue)"),
+        Some(45.0)
+    );
+    assert_eq!(angle("linear-gradient(45deg, red, blue)"), Some(45.0));
+    assert_eq!(angle("linear-gradient(0.5turn, red, blue)"), Some(180.0));
+    assert_eq!(angle("linear-gradient(to nowhere, red, blue)"), None);
+}
+
+#[test]
+fn parse_gradient_stops() {
+    let fill = parse_fill("linear-gradient(red, #00ff00 40%, blue, white, black 100%)");
+    let offsets = match fill {
+        Some(Fill::LinearGradient(gradient)) => gradient
+            .stops
+            .iter()
+            .map(|stop| stop.offset)
+            .collect::<Vec<f32>>(),
+        _ => Vec::new(),
+    };
+    assert_eq!(offsets, vec![0.0, 0.4, 0.6, 0.8, 1.0]);
+}
+
+#[test]
+fn parse_gradient_stops_backwards() {
+    let fill = parse_fill("linear-gradient(red 50%, blue 20%)");
+    let offsets = match fill {
+        Some(Fill::LinearGradient(gradient)) => gradient
+            .stops
+            .iter()
+            .map(|stop| stop.offset)
+            .collect::<Vec<f32>>(),
+        _ => Vec::new(),
+    };
+    assert_eq!(offsets, vec![0.5, 0.5]);
+}
+
+#[test]
+fn parse_radial_gradient() {
+    let fill = parse_fill("radial-gradient(circle, white, black 50%)");
+    assert_eq!(
+        fill,
+        Some(Fill::RadialGradient(RadialGradient {
+            stops: vec![
+                GradientStop {
+                    color: Color::WHITE,
+                    offset: 0.0
+                },
+                GradientStop {
+                    color: Color::BLACK,
+                    offset: 0.5
+                }
+            ]
+        }))
+    );
+}
+
+#[test]
+fn parse_conic_gradient() {
+    let fill = parse_fill("conic-gradient(from 90deg, red, blue)");
+    assert_eq!(
+        fill,
+        Some(Fill::ConicGradient(ConicGradient {
+            angle: 90.0,
+            stops: vec![
+                GradientStop {
+                    color: Color::RED,
+                    offset: 0.0
+                },
+                GradientStop {
+                    color: Color::BLUE,
+                    offset: 1.0
+                }
+            ]
+        }))
+    );
+}
+
+#[test]
+fn parse_invalid_gradients() {
+    assert_eq!(parse_fill("linear-gradient(red)"), None);
+    assert_eq!(parse_fill("linear-gradient(red, blue"), None);
+    assert_eq!(parse_fill("radial-gradient(red, notacolor)"), None);
+    assert_eq!(parse_fill("conic-gradient(from nowhere, red, blue)"), None);
+}
+
+#[test]
+fn display_gradient() {
+    let fill = parse_fill("linear-gradient(to right, red, blue 50%)").unwrap();
+    assert_eq!(
+        fill.to_string(),
+        "linear-gradient(90deg, #ff0000ff 0%, #0000ffff 50%)"
+    );
+    assert_eq!(parse_fill(&fill.to_string()), Some(fill));
+}
@@ -0,0 +1,44 @@
+use freya_node_state::{parse_blur, parse_filter_amount, Filters};
+
+#[test]
+fn parse_blurs() {
+    assert_eq!(parse_blur("4"), Some(4.0));
+    assert_eq!(parse_blur("2.5px"), Some(2.5));
+    assert_eq!(parse_blur("0"), Some(0.0));
+}
+
+#[test]
+fn parse_invalid_blurs() {
+    assert_eq!(parse_blur("-4"), None);
+    assert_eq!(parse_blur("4%"), None);
+    assert_eq!(parse_blur("blurry"), None);
+}
+
+#[test]
+fn parse_filter_amounts() {
+    assert_eq!(parse_filter_amount("0.5"), Some(0.5));
+    assert_eq!(parse_filter_amount("150%"), Some(1.5));
+    assert_eq!(parse_filter_amount("0"), Some(0.0));
+    assert_eq!(parse_filter_amount("-1"), None);
+    assert_eq!(parse_filter_amount("inf"), None);
+}
+
+#[test]
+fn empty_filters() {
+    assert!(Filters::default().is_empty());
+    assert!(!Filters::default().has_color_filter());
+
+    let blurred = Filters {
+        blur: 4.0,
+        ..Filters::default()
+    };
+    assert!(!blurred.is_empty());
+    assert!(!blurred.has_color_filter());
+
+    let grayed = Filters {
+        grayscale: 1.0,
+        ..Filters::default()
+    };
+    assert!(!grayed.is_empty());
+    assert!(grayed.has_color_filter());
+}
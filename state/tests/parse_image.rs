@@ -0,0 +1,92 @@
+use freya_node_state::{
+    parse_image_sampling, parse_image_slice, parse_object_fit, parse_object_position,
+    ImageSampling, ImageSlice, ObjectFit, ObjectPosition, OriginOffset,
+};
+
+#[test]
+fn parse_object_fits() {
+    assert_eq!(parse_object_fit("fill"), Some(ObjectFit::Fill));
+    assert_eq!(parse_object_fit("contain"), Some(ObjectFit::Contain));
+    assert_eq!(parse_object_fit("cover"), Some(ObjectFit::Cover));
+    assert_eq!(parse_object_fit("none"), Some(ObjectFit::None));
+    assert_eq!(parse_object_fit("scale-down"), Some(ObjectFit::ScaleDown));
+    assert_eq!(parse_object_fit("stretch"), None);
+}
+
+#[test]
+fn fit_images() {
+    let image = (200.0, 100.0);
+    let area = (100.0, 100.0);
+
+    assert_eq!(ObjectFit::Fill.fit(image, area), (100.0, 100.0));
+    assert_eq!(ObjectFit::Contain.fit(image, area), (100.0, 50.0));
+    assert_eq!(ObjectFit::Cover.fit(image, area), (200.0, 100.0));
+    assert_eq!(ObjectFit::None.fit(image, area), (200.0, 100.0));
+    assert_eq!(ObjectFit::ScaleDown.fit(image, area), (100.0, 50.0));
+    assert_eq!(ObjectFit::ScaleDown.fit((50.0, 20.0), area), (50.0, 20.0));
+}
+
+#[test]
+fn parse_object_positions() {
+    assert_eq!(
+        parse_object_position("center"),
+        Some(ObjectPosition::default())
+    );
+    assert_eq!(
+        parse_object_position("top left"),
+        Some(ObjectPosition {
+            x: OriginOffset::Percentage(0.0),
+            y: OriginOffset::Percentage(0.0),
+        })
+    );
+    assert_eq!(
+        parse_object_position("10 75%"),
+        Some(ObjectPosition {
+            x: OriginOffset::Pixels(10.0),
+            y: OriginOffset::Percentage(75.0),
+        })
+    );
+    assert_eq!(parse_object_position("top bottom"), None);
+}
+
+#[test]
+fn parse_image_samplings() {
+    assert_eq!(
+        parse_image_sampling("nearest"),
+        Some(ImageSampling::Nearest)
+    );
+    assert_eq!(parse_image_sampling("linear"), Some(ImageSampling::Linear));
+    assert_eq!(parse_image_sampling("mipmap"), Some(ImageSampling::Mipmap));
+    assert_eq!(parse_image_sampling("cubic"), Some(ImageSampling::Cubic));
+    assert_eq!(parse_image_sampling("best"), None);
+}
+
+#[test]
+fn parse_image_slices() {
+    assert_eq!(
+        parse_image_slice("16"),
+        Some(ImageSlice {
+            left: 16,
+            top: 16,
+            right: 16,
+            bottom: 16,
+        })
+    );
+    assert_eq!(
+        parse_image_slice("8 4 8 12"),
+        Some(ImageSlice {
+            left: 8,
+            top: 4,
+            right: 8,
+            bottom: 12,
+        })
+    );
+}
+
+#[test]
+fn parse_invalid_image_slices() {
+    assert_eq!(parse_image_slice("8 4"), None);
+    assert_eq!(parse_image_slice("-8"), None);
+    assert_eq!(parse_image_slice("8.5"), None);
+    assert_eq!(parse_image_slice(""), None);
+}
@@ -0,0 +1,39 @@
+use freya_node_state::{parse_measures, parse_padding};
+
+#[test]
+fn parse_all_measures() {
+    let measures = parse_measures("10");
+    assert_eq!(measures, Some((10.0, 10.0, 10.0, 10.0)));
+}
+
+#[test]
+fn parse_vertical_horizontal_measures() {
+    let measures = parse_measures("10 20");
+    assert_eq!(measures, Some((10.0, 20.0, 10.0, 20.0)));
+}
+
+#[test]
+fn parse_top_horizontal_bottom_measures() {
+    let measures = parse_measures("5 10 15");
+    assert_eq!(measures, Some((5.0, 10.0, 15.0, 10.0)));
+}
+
+#[test]
+fn parse_every_side_measures() {
+    let measures = parse_measures("5 10 15 20");
+    assert_eq!(measures, Some((5.0, 10.0, 15.0, 20.0)));
+}
+
+#[test]
+fn parse_invalid_measures() {
+    assert_eq!(parse_measures("1O"), None);
+    assert_eq!(parse_measures(""), None);
+    assert_eq!(parse_measures("1 2 3 4 5"), None);
+}
+
+#[test]
+fn parse_single_value_padding() {
+    assert_eq!(parse_padding("10"), Some((5.0, 5.0, 5.0, 5.0)));
+    assert_eq!(parse_padding("10 20"), Some((10.0, 20.0, 10.0, 20.0)));
+    assert_eq!(parse_padding("1O"), None);
+}
@@ -0,0 +1,63 @@
+use freya_node_state::{parse_radius, CornerRadius};
+
+#[test]
+fn parse_all_radius() {
+    let radius = parse_radius("8");
+    assert_eq!(
+        radius,
+        Some(CornerRadius {
+            top_left: 8.0,
+            top_right: 8.0,
+            bottom_right: 8.0,
+            bottom_left: 8.0,
+        })
+    );
+}
+
+#[test]
+fn parse_diagonal_radius() {
+    let radius = parse_radius("8 4");
+    assert_eq!(
+        radius,
+        Some(CornerRadius {
+            top_left: 8.0,
+            top_right: 4.0,
+            bottom_right: 8.0,
+            bottom_left: 4.0,
+        })
+    );
+}
+
+#[test]
+fn parse_three_radius() {
+    let radius = parse_radius("8 4 2");
+    assert_eq!(
+        radius,
+        Some(CornerRadius {
+            top_left: 8.0,
+            top_right: 4.0,
+            bottom_right: 2.0,
+            bottom_left: 4.0,
+        })
+    );
+}
+
+#[test]
+fn parse_corners_radius() {
+    let radius = parse_radius("8 8 0 0");
+    assert_eq!(
+        radius,
+        Some(CornerRadius {
+            top_left: 8.0,
+            top_right: 8.0,
+            bottom_right: 0.0,
+            bottom_left: 0.0,
+        })
+    );
+}
+
+#[test]
+fn parse_invalid_radius() {
+    assert_eq!(parse_radius("8 8 0 0 0"), None);
+    assert_eq!(parse_radius("big"), None);
+}
@@ -1,4 +1,4 @@
-use freya_node_state::{parse_shadow, ShadowSettings};
+use freya_node_state::{parse_shadow, parse_shadows, ShadowSettings};
 use skia_safe::Color;
 
 #[test]
@@ -10,8 +10,86 @@ fn parse_big_shadow() {
             x: 1.0,
             y: 2.0,
             intensity: 50,
-            size: 25.0,
-            color: Color::RED
+            blur: 25.0,
+            spread: 0.0,
+            color: Color::RED,
+            inset: false
         })
     );
 }
+
+#[test]
+fn parse_shadow_with_spread() {
+    let shadow = parse_shadow("0 4 80 10 -2 rgb(0, 0, 255)");
+    assert_eq!(
+        shadow,
+        Some(ShadowSettings {
+            x: 0.0,
+            y: 4.0,
+            intensity: 80,
+            blur: 10.0,
+            spread: -2.0,
+            color: Color::BLUE,
+            inset: false
+        })
+    );
+}
+
+#[test]
+fn parse_inset_shadow() {
+    let shadow = parse_shadow("inset 0 0 120 8 2 black");
+    assert_eq!(
+        shadow,
+        Some(ShadowSettings {
+            x: 0.0,
+            y: 0.0,
+            intensity: 120,
+            blur: 8.0,
+            spread: 2.0,
+            color: Color::BLACK,
+            inset: true
+        })
+    );
+}
+
+#[test]
+fn parse_multiple_shadows() {
+    let shadows = parse_shadows("0 2 60 4 black, inset 0 0 30 8 rgb(255, 255, 255)");
+    assert_eq!(
+        shadows,
+        Some(vec![
+            ShadowSettings {
+                x: 0.0,
+                y: 2.0,
+                intensity: 60,
+                blur: 4.0,
+                spread: 0.0,
+                color: Color::BLACK,
+                inset: false
+            },
+            ShadowSettings {
+                x: 0.0,
+                y: 0.0,
+                intensity: 30,
+                blur: 8.0,
+                spread: 0.0,
+                color: Color::WHITE,
+                inset: true
+            }
+        ])
+    );
+}
+
+#[test]
+fn parse_no_shadows() {
+    let shadows = parse_shadows("none");
+    assert_eq!(shadows, Some(Vec::new()));
+}
+
+#[test]
+fn parse_invalid_shadows() {
+    assert_eq!(parse_shadows("0 2 60 4 black,"), None);
+    assert_eq!(parse_shadows("0 2 300 4 black"), None);
+    assert_eq!(parse_shadows("0 2 60 black"), None);
+    assert_eq!(parse_shadows("0 2 60 4 2 black red"), None);
+}
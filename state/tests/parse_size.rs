@@ -1,21 +1,21 @@
-use freya_node_state::{parse_size, CalcType, SizeMode};
+use freya_node_state::{parse_size, CalcType, ParseCalcError, SizeMode};
 
 #[test]
 fn parse_manual_size() {
     let size = parse_size("123");
-    assert_eq!(size, Some(SizeMode::Manual(123.0)));
+    assert_eq!(size, Ok(SizeMode::Manual(123.0)));
 }
 
 #[test]
 fn parse_relative_size() {
     let size = parse_size("78.123%");
-    assert_eq!(size, Some(SizeMode::Percentage(78.123)));
+    assert_eq!(size, Ok(SizeMode::Percentage(78.123)));
 }
 
 #[test]
 fn parse_auto_size() {
     let size = parse_size("auto");
-    assert_eq!(size, Some(SizeMode::Auto));
+    assert_eq!(size, Ok(SizeMode::Auto));
 }
 
 #[test]
@@ -23,12 +23,66 @@ fn parse_calc_size() {
     let size = parse_size("calc(90% - 5% * 123.6)");
     assert_eq!(
         size,
-        Some(SizeMode::Calculation(vec![
-            CalcType::Percentage(90.0),
-            CalcType::Sub,
-            CalcType::Percentage(5.0),
-            CalcType::Mul,
-            CalcType::Manual(123.6)
-        ]))
+        Ok(SizeMode::Calculation(CalcType::Sub(
+            Box::new(CalcType::Percentage(90.0)),
+            Box::new(CalcType::Mul(
+                Box::new(CalcType::Percentage(5.0)),
+                Box::new(CalcType::Manual(123.6))
+            ))
+        )))
     );
 }
+
+#[test]
+fn parse_flex_size() {
+    let size = parse_size("flex(2.5)");
+    assert_eq!(size, Ok(SizeMode::Flex(2.5)));
+}
+
+#[test]
+fn parse_invalid_flex_size() {
+    let size = parse_size("flex(-1)");
+    assert_eq!(
+        size,
+        Err(ParseCalcError::InvalidNumber("flex(-1)".to_string()))
+    );
+}
+
+#[test]
+fn parse_pixels_size() {
+    let size = parse_size("10px");
+    assert_eq!(size, Ok(SizeMode::Manual(10.0)));
+}
+
+#[test]
+fn parse_viewport_size() {
+    let size = parse_size("50vw");
+    assert_eq!(
+        size,
+        Ok(SizeMode::Calculation(CalcType::ViewportWidth(50.0)))
+    );
+}
+
+#[test]
+fn parse_font_relative_size() {
+    let size = parse_size("calc(2em + 1.5rem)");
+    assert_eq!(
+        size,
+        Ok(SizeMode::Calculation(CalcType::Add(
+            Box::new(CalcType::Em(2.0)),
+            Box::new(CalcType::Rem(1.5))
+        )))
+    );
+}
+
+#[test]
+fn parse_unknown_unit_size() {
+    let size = parse_size("10pt");
+    assert_eq!(size, Err(ParseCalcError::UnknownUnit("pt".to_string())));
+}
+
+#[test]
+fn parse_invalid_calc_size() {
+    let size = parse_size("calc(100 -");
+    assert_eq!(size, Err(ParseCalcError::UnexpectedEnd));
+}
@@ -0,0 +1,80 @@
+use freya_node_state::{
+    parse_decoration, parse_text_shadow, parse_text_shadows, Decoration, DecorationStyle,
+    TextShadow,
+};
+use skia_safe::Color;
+
+#[test]
+fn parse_decorations() {
+    assert_eq!(
+        parse_decoration("underline"),
+        Some(Decoration {
+            underline: true,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        parse_decoration("underline line-through wavy rgb(255, 0, 0)"),
+        Some(Decoration {
+            underline: true,
+            line_through: true,
+            style: DecorationStyle::Wavy,
+            color: Some(Color::RED),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        parse_decoration("dashed overline"),
+        Some(Decoration {
+            overline: true,
+            style: DecorationStyle::Dashed,
+            ..Default::default()
+        })
+    );
+    assert_eq!(parse_decoration("none"), Some(Decoration::default()));
+}
+
+#[test]
+fn parse_invalid_decorations() {
+    assert_eq!(parse_decoration("underlined"), None);
+    assert_eq!(parse_decoration("dotted red"), None);
+    assert_eq!(parse_decoration(""), None);
+}
+
+#[test]
+fn parse_text_shadows_list() {
+    assert_eq!(
+        parse_text_shadow("1 2 3 black"),
+        Some(TextShadow {
+            x: 1.0,
+            y: 2.0,
+            blur: 3.0,
+            color: Color::BLACK,
+        })
+    );
+    assert_eq!(
+        parse_text_shadows("1 1 0 black, 0 0 8 rgb(0, 0, 255)"),
+        Some(vec![
+            TextShadow {
+                x: 1.0,
+                y: 1.0,
+                blur: 0.0,
+                color: Color::BLACK,
+            },
+            TextShadow {
+                x: 0.0,
+                y: 0.0,
+                blur: 8.0,
+                color: Color::BLUE,
+            }
+        ])
+    );
+    assert_eq!(parse_text_shadows("none"), Some(Vec::new()));
+}
+
+#[test]
+fn parse_invalid_text_shadows() {
+    assert_eq!(parse_text_shadows("1 1 0 black,"), None);
+    assert_eq!(parse_text_shadows("1 1 -2 black"), None);
+    assert_eq!(parse_text_shadows("1 1 black"), None);
+}
@@ -0,0 +1,52 @@
+use freya_node_state::{parse_tracks, CalcType, ParseCalcError, SizeMode};
+
+#[test]
+fn parse_single_track() {
+    let tracks = parse_tracks("100");
+    assert_eq!(tracks, Ok(vec![SizeMode::Manual(100.0)]));
+}
+
+#[test]
+fn parse_mixed_tracks() {
+    let tracks = parse_tracks("100, 25%, auto, flex(2), calc(50% - 20)");
+    assert_eq!(
+        tracks,
+        Ok(vec![
+            SizeMode::Manual(100.0),
+            SizeMode::Percentage(25.0),
+            SizeMode::Auto,
+            SizeMode::Flex(2.0),
+            SizeMode::Calculation(CalcType::Sub(
+                Box::new(CalcType::Percentage(50.0)),
+                Box::new(CalcType::Manual(20.0))
+            ))
+        ])
+    );
+}
+
+#[test]
+fn parse_function_tracks() {
+    let tracks = parse_tracks("calc(max(100, 20%)), 50");
+    assert_eq!(
+        tracks,
+        Ok(vec![
+            SizeMode::Calculation(CalcType::Max(vec![
+                CalcType::Manual(100.0),
+                CalcType::Percentage(20.0)
+            ])),
+            SizeMode::Manual(50.0)
+        ])
+    );
+}
+
+#[test]
+fn parse_invalid_tracks() {
+    let tracks = parse_tracks("100, freya!!");
+    assert_eq!(
+        tracks,
+        Err(ParseCalcError::UnexpectedToken {
+            position: 5,
+            token: "!".to_string()
+        })
+    );
+}
@@ -0,0 +1,111 @@
+use freya_node_state::{
+    parse_transform, parse_transform_origin, OriginOffset, Transform, TransformOrigin,
+};
+
+#[test]
+fn parse_translate() {
+    assert_eq!(
+        parse_transform("translate(10, -5px)"),
+        Some(vec![Transform::Translate(10.0, -5.0)])
+    );
+    assert_eq!(
+        parse_transform("translateY(20)"),
+        Some(vec![Transform::Translate(0.0, 20.0)])
+    );
+}
+
+#[test]
+fn parse_scale() {
+    assert_eq!(
+        parse_transform("scale(1.5)"),
+        Some(vec![Transform::Scale(1.5, 1.5)])
+    );
+    assert_eq!(
+        parse_transform("scale(2, 0.5)"),
+        Some(vec![Transform::Scale(2.0, 0.5)])
+    );
+    assert_eq!(
+        parse_transform("scaleX(-1)"),
+        Some(vec![Transform::Scale(-1.0, 1.0)])
+    );
+}
+
+#[test]
+fn parse_rotate() {
+    assert_eq!(
+        parse_transform("rotate(45deg)"),
+        Some(vec![Transform::Rotate(45.0)])
+    );
+    assert_eq!(
+        parse_transform("rotate(0.5turn)"),
+        Some(vec![Transform::Rotate(180.0)])
+    );
+}
+
+#[test]
+fn parse_multiple_transforms() {
+    assert_eq!(
+        parse_transform("translate(10, 0)  rotate(90deg) scale(2)"),
+        Some(vec![
+            Transform::Translate(10.0, 0.0),
+            Transform::Rotate(90.0),
+            Transform::Scale(2.0, 2.0)
+        ])
+    );
+    assert_eq!(parse_transform("none"), Some(Vec::new()));
+}
+
+#[test]
+fn parse_invalid_transforms() {
+    assert_eq!(parse_transform("skew(10deg)"), None);
+    assert_eq!(parse_transform("scale(1, 2, 3)"), None);
+    assert_eq!(parse_transform("rotate(45deg"), None);
+    assert_eq!(parse_transform("translate(10%, 0)"), None);
+}
+
+#[test]
+fn parse_origin_keywords() {
+    assert_eq!(
+        parse_transform_origin("center"),
+        Some(TransformOrigin::default())
+    );
+    assert_eq!(
+        parse_transform_origin("top left"),
+        Some(TransformOrigin {
+            x: OriginOffset::Percentage(0.0),
+            y: OriginOffset::Percentage(0.0)
+        })
+    );
+    assert_eq!(
+        parse_transform_origin("right bottom"),
+        Some(TransformOrigin {
+            x: OriginOffset::Percentage(100.0),
+            y: OriginOffset::Percentage(100.0)
+        })
+    );
+    assert_eq!(
+        parse_transform_origin("bottom"),
+        Some(TransformOrigin {
+            x: OriginOffset::Percentage(50.0),
+            y: OriginOffset::Percentage(100.0)
+        })
+    );
+}
+
+#[test]
+fn parse_origin_offsets() {
+    assert_eq!(
+        parse_transform_origin("25% 10"),
+        Some(TransformOrigin {
+            x: OriginOffset::Percentage(25.0),
+            y: OriginOffset::Pixels(10.0)
+        })
+    );
+}
+
+#[test]
+fn parse_invalid_origin() {
+    assert_eq!(parse_transform_origin("top bottom"), None);
+    assert_eq!(parse_transform_origin("left left"), None);
+    assert_eq!(parse_transform_origin("1 2 3"), None);
+}
@@ -0,0 +1,22 @@
+use freya_node_state::{try_parse_wrap, UnknownKeyword, WrapMode};
+
+#[test]
+fn parse_wrap_wrap() {
+    let wrap = try_parse_wrap("wrap");
+    assert_eq!(wrap, Ok(WrapMode::Wrap));
+}
+
+#[test]
+fn parse_fallback_wrap() {
+    let wrap = try_parse_wrap("freya!!").unwrap_or_default();
+    assert_eq!(wrap, WrapMode::NoWrap);
+}
+
+#[test]
+fn try_parse_wrap_modes() {
+    assert_eq!(try_parse_wrap("nowrap"), Ok(WrapMode::NoWrap));
+    assert_eq!(
+        try_parse_wrap("freya!!"),
+        Err(UnknownKeyword("freya!!".to_string()))
+    );
+}
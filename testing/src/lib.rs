@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 use anymap::AnyMap;
@@ -5,10 +6,18 @@ use dioxus_core::{Component, ElementId, VirtualDom};
 use dioxus_native_core::real_dom::{Node, NodeType, RealDom};
 use dioxus_native_core::traversable::Traversable;
 use freya_common::{LayoutMemorizer, NodeArea};
-use freya_node_state::NodeState;
+use freya_node_state::{AttributeError, NodeState, SafeDiagnostics};
 use freya_processor::events::{EventsProcessor, FreyaEvent};
-use freya_processor::{process_work, SafeEventEmitter, SafeFreyaEvents};
-use skia_safe::textlayout::FontCollection;
+use freya_processor::{
+    process_work, SafeDOM, SafeEventEmitter, SafeFreyaEvents, SafeLayoutManager,
+};
+use freya_renderer::{create_raster_surface, render_step, ImagesCache, WindowConfig};
+use skia_safe::textlayout::{FontCollection, TypefaceFontProvider};
+use skia_safe::{Color, Data, FontMgr, Image, Typeface};
+
+mod snapshot;
+
+pub use snapshot::*;
 
 pub struct TestNode {
     #[allow(dead_code)]
@@ -67,6 +76,8 @@ pub struct TestUtils {
     event_emitter: SafeEventEmitter,
     events_processor: Arc<Mutex<EventsProcessor>>,
     font_collection: FontCollection,
+    images_cache: Arc<Mutex<ImagesCache>>,
+    diagnostics: SafeDiagnostics,
 }
 
 impl TestUtils {
@@ -85,10 +96,13 @@ impl TestUtils {
 
         let mut ctx = AnyMap::new();
 
-        ctx.insert(self.layout_memorizer.clone());
+        ctx.insert(self.diagnostics.clone());
+        ctx.insert((self.layout_memorizer.clone(), self.diagnostics.clone()));
 
         if !to_update.is_empty() {
-            self.rdom.lock().unwrap().update_state(&dom, to_update, ctx);
+            let mut rdom = self.rdom.lock().unwrap();
+            rdom.update_state(&dom, to_update, ctx);
+            self.diagnostics.lock().unwrap().remove_missing_nodes(&rdom);
         }
     }
 
@@ -108,8 +122,104 @@ impl TestUtils {
             &mut self.events_processor.lock().unwrap(),
             &self.layout_memorizer,
             &mut (),
-            |_, _, _, _, _| {},
+            |_, _, _, _, _, _| {},
+        );
+    }
+
+    /// Render the current DOM into an in-memory raster surface
+    pub fn render_frame(&mut self, sizes: (f32, f32)) -> Image {
+        let mut surface = create_raster_surface(sizes.0 as u32, sizes.1 as u32);
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        let mut images_cache = self.images_cache.lock().unwrap();
+
+        process_work(
+            &self.rdom,
+            NodeArea {
+                width: sizes.0,
+                height: sizes.1,
+                x: 0.0,
+                y: 0.0,
+            },
+            self.freya_events.clone(),
+            &self.event_emitter,
+            &mut self.font_collection,
+            &mut self.events_processor.lock().unwrap(),
+            &self.layout_memorizer,
+            canvas,
+            |dom, step, font_collection, viewports_collection, transforms_collection, canvas| {
+                render_step(
+                    dom,
+                    canvas,
+                    step,
+                    font_collection,
+                    viewports_collection,
+                    transforms_collection,
+                    &mut images_cache,
+                    &self.diagnostics,
+                );
+            },
         );
+
+        images_cache.collect_unused(&self.rdom.lock().unwrap());
+
+        surface.image_snapshot()
+    }
+
+    /// Render the current DOM and get it's pixels
+    pub fn render_pixels(&mut self, sizes: (f32, f32)) -> FramePixels {
+        let frame = self.render_frame(sizes);
+        FramePixels::from_image(&frame).expect("Failed to read the frame pixels.")
+    }
+
+    /// Render the current DOM and encode the frame as PNG
+    pub fn render_png(&mut self, sizes: (f32, f32)) -> Vec<u8> {
+        self.render_pixels(sizes)
+            .encode_png()
+            .expect("Failed to encode the frame.")
+    }
+
+    /// Render the current DOM and compare it against a golden PNG image,
+    /// see [`assert_frame_matches`] for more details.
+    pub fn assert_snapshot(&mut self, sizes: (f32, f32), golden: impl AsRef<Path>, tolerance: u8) {
+        let frame = self.render_pixels(sizes);
+        assert_frame_matches(&frame, golden.as_ref(), tolerance);
+    }
+
+    /// Get the DOM as a window that can be passed to [`freya_renderer::run_offscreen`]
+    pub fn offscreen_window<T: Clone>(
+        &self,
+        win_config: WindowConfig<T>,
+    ) -> (
+        SafeDOM,
+        SafeEventEmitter,
+        SafeLayoutManager,
+        SafeDiagnostics,
+        WindowConfig<T>,
+    ) {
+        (
+            self.rdom.clone(),
+            self.event_emitter.clone(),
+            self.layout_memorizer.clone(),
+            self.diagnostics.clone(),
+            win_config,
+        )
+    }
+
+    /// Register a font under the given family, so text can be rendered the same
+    /// no matter which fonts are installed in the system
+    pub fn register_font(&mut self, font_data: &[u8], family: &str) {
+        let typeface =
+            Typeface::from_data(Data::new_copy(font_data), None).expect("Failed to load the font.");
+        let mut font_provider = TypefaceFontProvider::new();
+        font_provider.register_typeface(typeface, Some(family));
+        self.font_collection
+            .set_asset_font_manager(Some(FontMgr::from(font_provider)));
+    }
+
+    /// Get the attribute errors reported by the nodes of the DOM
+    pub fn attribute_errors(&self) -> Vec<AttributeError> {
+        self.diagnostics.lock().unwrap().errors().to_vec()
     }
 
     pub fn send_event(&mut self, event: FreyaEvent) {
@@ -150,13 +260,16 @@ pub fn launch_test(root: Component<()>) -> TestUtils {
     let layout_memorizer = Arc::new(Mutex::new(LayoutMemorizer::new()));
     let freya_events = Arc::new(Mutex::new(Vec::new()));
     let events_processor = Arc::new(Mutex::new(EventsProcessor::default()));
-    let font_collection = FontCollection::new();
+    let diagnostics = SafeDiagnostics::default();
+    let mut font_collection = FontCollection::new();
+    font_collection.set_default_font_manager(FontMgr::default(), "Fira Sans");
 
     let muts = dom.rebuild();
     let to_update = rdom.lock().unwrap().apply_mutations(vec![muts]);
     let mut ctx = AnyMap::new();
 
-    ctx.insert(layout_memorizer.clone());
+    ctx.insert(diagnostics.clone());
+    ctx.insert((layout_memorizer.clone(), diagnostics.clone()));
 
     rdom.lock().unwrap().update_state(&dom, to_update, ctx);
 
@@ -173,5 +286,7 @@ pub fn launch_test(root: Component<()>) -> TestUtils {
         freya_events,
         events_processor,
         font_collection,
+        images_cache: Arc::default(),
+        diagnostics,
     }
 }
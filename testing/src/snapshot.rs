@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+
+use skia_safe::{AlphaType, ColorType, Data, EncodedImageFormat, Image, ImageInfo};
+
+/// Set this environment variable to overwrite the golden images with the rendered frames.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "FREYA_UPDATE_SNAPSHOTS";
+
+/// Unpremultiplied RGBA pixels of a rendered frame
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FramePixels {
+    pub width: i32,
+    pub height: i32,
+    pub pixels: Vec<u8>,
+}
+
+impl FramePixels {
+    /// Read the pixels of an image
+    pub fn from_image(image: &Image) -> Option<Self> {
+        let info = rgba_info(image.width(), image.height());
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; row_bytes * image.height() as usize];
+        let read = image.read_pixels(
+            &info,
+            &mut pixels,
+            row_bytes,
+            (0, 0),
+            skia_safe::image::CachingHint::Allow,
+        );
+
+        if read {
+            Some(Self {
+                width: image.width(),
+                height: image.height(),
+                pixels,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Decode a PNG (or any other format supported by Skia)
+    pub fn from_encoded(bytes: &[u8]) -> Option<Self> {
+        let image = Image::from_encoded(Data::new_copy(bytes))?;
+        Self::from_image(&image)
+    }
+
+    /// Get the RGBA value of the pixel at the given position
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = ((y * self.width + x) * 4) as usize;
+        Some([
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ])
+    }
+
+    /// Encode the pixels as PNG
+    pub fn encode_png(&self) -> Option<Vec<u8>> {
+        let info = rgba_info(self.width, self.height);
+        let row_bytes = info.min_row_bytes();
+        let image = Image::from_raster_data(&info, Data::new_copy(&self.pixels), row_bytes)?;
+        let data = image.encode_to_data(EncodedImageFormat::PNG)?;
+        Some(data.as_bytes().to_vec())
+    }
+}
+
+/// Result of comparing two frames
+pub struct FrameDiff {
+    /// Amount of pixels that exceed the tolerance
+    pub mismatched_pixels: usize,
+    /// Frame highlighting in red the mismatched pixels
+    pub diff: FramePixels,
+}
+
+/// Compare two frames pixel by pixel, a pixel mismatches if any of it's channels
+/// differs more than `tolerance`. Returns `None` if the frames sizes don't match.
+pub fn diff_frames(
+    expected: &FramePixels,
+    actual: &FramePixels,
+    tolerance: u8,
+) -> Option<FrameDiff> {
+    if expected.width != actual.width || expected.height != actual.height {
+        return None;
+    }
+
+    let mut mismatched_pixels = 0;
+    let mut diff_pixels = Vec::with_capacity(actual.pixels.len());
+
+    for (expected, actual) in expected
+        .pixels
+        .chunks_exact(4)
+        .zip(actual.pixels.chunks_exact(4))
+    {
+        let matches = expected
+            .iter()
+            .zip(actual)
+            .all(|(e, a)| e.abs_diff(*a) <= tolerance);
+
+        if matches {
+            // Dimmed version of the original pixel
+            diff_pixels.extend_from_slice(&[actual[0] / 4, actual[1] / 4, actual[2] / 4, 255]);
+        } else {
+            mismatched_pixels += 1;
+            diff_pixels.extend_from_slice(&[255, 0, 0, 255]);
+        }
+    }
+
+    Some(FrameDiff {
+        mismatched_pixels,
+        diff: FramePixels {
+            width: actual.width,
+            height: actual.height,
+            pixels: diff_pixels,
+        },
+    })
+}
+
+/// Compare a frame against the golden image stored in `golden`.
+///
+/// The golden image is only written when [`UPDATE_SNAPSHOTS_ENV`] is set, a missing golden image is a failure.
+/// On mismatch a diff image is written next to the golden image and the function panics.
+pub fn assert_frame_matches(actual: &FramePixels, golden: &Path, tolerance: u8) {
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        let png = actual.encode_png().expect("Failed to encode the frame.");
+        if let Some(dir) = golden.parent() {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(golden, png).unwrap();
+        return;
+    }
+
+    if !golden.exists() {
+        panic!(
+            "Golden image {} doesn't exist, run the test with {UPDATE_SNAPSHOTS_ENV} set to create it",
+            golden.display()
+        );
+    }
+
+    let golden_bytes = std::fs::read(golden).unwrap();
+    let expected = FramePixels::from_encoded(&golden_bytes)
+        .unwrap_or_else(|| panic!("Failed to decode golden image {}", golden.display()));
+
+    let diff = diff_frames(&expected, actual, tolerance).unwrap_or_else(|| {
+        panic!(
+            "Frame size {}x{} doesn't match the golden image {} size {}x{}",
+            actual.width,
+            actual.height,
+            golden.display(),
+            expected.width,
+            expected.height
+        )
+    });
+
+    if diff.mismatched_pixels > 0 {
+        let diff_path = diff_path(golden);
+        let png = diff.diff.encode_png().expect("Failed to encode the diff.");
+        std::fs::write(&diff_path, png).unwrap();
+        panic!(
+            "{} pixels don't match the golden image {}, see {}",
+            diff.mismatched_pixels,
+            golden.display(),
+            diff_path.display()
+        );
+    }
+}
+
+fn diff_path(golden: &Path) -> PathBuf {
+    let stem = golden
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    golden.with_file_name(format!("{stem}.diff.png"))
+}
+
+fn rgba_info(width: i32, height: i32) -> ImageInfo {
+    ImageInfo::new(
+        (width, height),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        None,
+    )
+}
@@ -1,8 +1,23 @@
 use dioxus::prelude::*;
 use freya_elements as dioxus_elements;
+use freya_hooks::{use_image, ImageState};
+use freya_node_state::{AttributeErrorReason, ParseCalcError};
 use freya_processor::events::FreyaEvent;
-use freya_testing::launch_test;
+use freya_renderer::{run_offscreen, OffscreenController, RenderBackend, WindowConfig};
+use freya_testing::{launch_test, FramePixels};
 use glutin::event::MouseButton;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Whether every channel of a pixel is within the tolerance of the expected one
+fn pixel_is_near(pixel: Option<[u8; 4]>, expected: [u8; 4], tolerance: u8) -> bool {
+    let pixel = pixel.unwrap();
+    pixel
+        .iter()
+        .zip(expected)
+        .all(|(a, b)| a.abs_diff(b) <= tolerance)
+}
 
 #[tokio::test]
 async fn no_state() {
@@ -114,3 +129,661 @@ async fn simulate_events() {
 
     assert_eq!(text.text(), Some("Is enabled? true"));
 }
+
+#[tokio::test]
+async fn render_pixels() {
+    fn stateful_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "50%",
+            height: "100%",
+            background: "red"
+        })
+    }
+
+    let mut utils = launch_test(stateful_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    assert_eq!(frame.pixel(25, 50), Some([255, 0, 0, 255]));
+    assert_eq!(frame.pixel(75, 50), Some([255, 255, 255, 255]));
+}
+
+#[tokio::test]
+async fn match_snapshot() {
+    fn stateful_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "100%",
+            height: "50%",
+            background: "blue"
+        })
+    }
+
+    let mut utils = launch_test(stateful_app);
+
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/match_snapshot.png");
+    utils.assert_snapshot((100.0, 100.0), &golden, 0);
+
+    let png = utils.render_png((100.0, 100.0));
+    let decoded = FramePixels::from_encoded(&png).unwrap();
+    assert_eq!((decoded.width, decoded.height), (100, 100));
+}
+
+#[tokio::test]
+async fn report_attribute_errors() {
+    fn invalid_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "50%",
+            padding: "1O",
+            min_height: "calc(100% -)",
+            direction: "diagonal",
+            cursor_mode: "selectable",
+        })
+    }
+
+    let mut utils = launch_test(invalid_app);
+
+    utils.wait_for_work((500.0, 500.0)).await;
+
+    let errors = utils.attribute_errors();
+    let padding_error = errors.iter().find(|e| e.attribute == "padding").unwrap();
+    let min_height_error = errors.iter().find(|e| e.attribute == "min_height").unwrap();
+
+    assert_eq!(padding_error.element, "rect");
+    assert_eq!(padding_error.value, "1O");
+    assert_eq!(padding_error.reason, AttributeErrorReason::InvalidValue);
+    assert_eq!(min_height_error.value, "calc(100% -)");
+    assert_eq!(
+        min_height_error.reason,
+        AttributeErrorReason::InvalidCalculation(ParseCalcError::UnexpectedToken {
+            position: 11,
+            token: ")".to_string()
+        })
+    );
+    assert!(errors
+        .iter()
+        .any(|e| e.attribute == "direction" && e.reason == AttributeErrorReason::InvalidValue));
+    assert!(errors
+        .iter()
+        .any(|e| e.attribute == "cursor_mode" && e.reason == AttributeErrorReason::InvalidValue));
+    assert!(!errors.iter().any(|e| e.attribute == "width"));
+
+    let rect = utils.root().child(0).unwrap();
+
+    assert_eq!(rect.layout().unwrap().width, 250.0);
+}
+
+#[tokio::test]
+async fn forget_attribute_errors_of_removed_nodes() {
+    fn removable_app(cx: Scope) -> Element {
+        let show = use_state(&cx, || true);
+        render!(
+            container {
+                width: "100%",
+                height: "100%",
+                onclick: |_| {
+                    show.set(false);
+                },
+                (*show.get()).then_some({
+                    rsx!(rect {
+                        display: "flex",
+                    })
+                })
+            }
+        )
+    }
+
+    let mut utils = launch_test(removable_app);
+    // Errors of other DOMs aren't mixed
+    let other_utils = launch_test(removable_app);
+
+    utils.wait_for_work((500.0, 500.0)).await;
+
+    assert!(utils
+        .attribute_errors()
+        .iter()
+        .any(|e| e.attribute == "display"));
+
+    utils.send_event(FreyaEvent::Mouse {
+        name: "click",
+        cursor: (5.0, 5.0),
+        button: Some(MouseButton::Left),
+    });
+
+    utils.wait_for_update((500.0, 500.0)).await;
+
+    assert!(utils.attribute_errors().is_empty());
+    assert_eq!(other_utils.attribute_errors().len(), 1);
+}
+
+#[tokio::test]
+async fn inherit_opacity() {
+    fn faded_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "100%",
+            height: "100%",
+            background: "red",
+            opacity: "0.5",
+            rect {
+                width: "50%",
+                height: "100%",
+                background: "blue",
+                layer: "-5",
+            }
+        })
+    }
+
+    let mut utils = launch_test(faded_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    // Red over the white background
+    assert!(pixel_is_near(frame.pixel(75, 50), [255, 128, 128, 255], 2));
+    // The child is faded along with its parent, even though it's in another layer,
+    // so the red under it doesn't show through
+    assert!(pixel_is_near(frame.pixel(25, 50), [128, 128, 255, 255], 2));
+}
+
+#[tokio::test]
+async fn filter_descendants_once() {
+    fn darkened_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "100%",
+            height: "100%",
+            background: "red",
+            brightness: "50%",
+            rect {
+                width: "50%",
+                height: "100%",
+                background: "blue",
+                layer: "-5",
+            }
+        })
+    }
+
+    let mut utils = launch_test(darkened_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    assert!(pixel_is_near(frame.pixel(75, 50), [128, 0, 0, 255], 2));
+    // The child is darkened along with its parent, but only once
+    assert!(pixel_is_near(frame.pixel(25, 50), [0, 0, 128, 255], 2));
+}
+
+#[tokio::test]
+async fn layers_inside_opacity_group() {
+    fn layered_app(cx: Scope) -> Element {
+        render!(
+            rect {
+                width: "100%",
+                height: "100%",
+                direction: "horizontal",
+                rect {
+                    width: "50%",
+                    height: "100%",
+                    opacity: "0.5",
+                    rect {
+                        width: "100%",
+                        height: "50%",
+                        background: "blue",
+                        layer: "-5",
+                        rect {
+                            width: "100%",
+                            height: "200%",
+                            background: "green",
+                        }
+                    }
+                    rect {
+                        width: "100%",
+                        height: "50%",
+                        background: "red",
+                    }
+                }
+                rect {
+                    width: "50%",
+                    height: "100%",
+                    background: "black",
+                }
+            }
+        )
+    }
+
+    let mut utils = launch_test(layered_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    // The descendant of the first child is raised above the second child,
+    // and both are faded once as a whole
+    assert!(pixel_is_near(frame.pixel(25, 25), [128, 192, 128, 255], 2));
+    assert!(pixel_is_near(frame.pixel(25, 75), [128, 192, 128, 255], 2));
+    // Elements outside of the group are not affected
+    assert_eq!(frame.pixel(75, 50), Some([0, 0, 0, 255]));
+}
+
+#[tokio::test]
+async fn click_transformed_element() {
+    fn transformed_app(cx: Scope) -> Element {
+        let clicks = use_state(&cx, || 0);
+        render!(
+            container {
+                width: "100%",
+                height: "100%",
+                rect {
+                    width: "50",
+                    height: "50",
+                    background: "red",
+                    transform: "translate(100, 0)",
+                    onclick: |_| {
+                        clicks.with_mut(|clicks| *clicks += 1);
+                    },
+                }
+                label {
+                    "Clicks: {clicks}"
+                }
+            }
+        )
+    }
+
+    let mut utils = launch_test(transformed_app);
+
+    let container = utils.root().child(0).unwrap();
+    let label = container.child(1).unwrap();
+
+    utils.wait_for_work((500.0, 500.0)).await;
+
+    // Where the rect would be without the transform
+    utils.send_event(FreyaEvent::Mouse {
+        name: "click",
+        cursor: (25.0, 25.0),
+        button: Some(MouseButton::Left),
+    });
+
+    utils.wait_for_work((500.0, 500.0)).await;
+
+    // Where the rect is actually drawn
+    utils.send_event(FreyaEvent::Mouse {
+        name: "click",
+        cursor: (125.0, 25.0),
+        button: Some(MouseButton::Left),
+    });
+
+    utils.wait_for_update((500.0, 500.0)).await;
+
+    assert_eq!(label.child(0).unwrap().text(), Some("Clicks: 1"));
+}
+
+#[tokio::test]
+async fn tint_svg_with_current_color() {
+    static SQUARE: &[u8] = br#"<svg viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg"><rect width="10" height="10" fill="currentColor"/></svg>"#;
+
+    fn tinted_app(cx: Scope) -> Element {
+        render!(rect {
+            color: "blue",
+            svg {
+                svg_data: SQUARE,
+                width: "50",
+                height: "50",
+            }
+            svg {
+                svg_data: SQUARE,
+                color: "red",
+                width: "50",
+                height: "50",
+            }
+        })
+    }
+
+    let mut utils = launch_test(tinted_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    // Inherited from the parent
+    assert_eq!(frame.pixel(25, 25), Some([0, 0, 255, 255]));
+    // Set on the svg itself
+    assert_eq!(frame.pixel(25, 75), Some([255, 0, 0, 255]));
+}
+
+#[tokio::test]
+async fn offscreen_resize_and_close() {
+    fn stateful_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "50%",
+            height: "100%",
+            background: "red"
+        })
+    }
+
+    let utils = launch_test(stateful_app);
+
+    let controller = OffscreenController::new();
+    let frames = Arc::new(Mutex::new(Vec::new()));
+
+    let win_config = WindowConfig::<()>::builder()
+        .with_width(100)
+        .with_height(100)
+        .with_backend(RenderBackend::Raster)
+        .with_controller(controller.clone())
+        .with_on_frame({
+            let frames = frames.clone();
+            move |image| {
+                let mut frames = frames.lock().unwrap();
+                frames.push(FramePixels::from_image(image).unwrap());
+                // Resize after the first frame and close after the second one
+                if frames.len() == 1 {
+                    controller.resize(60, 40);
+                } else {
+                    controller.close();
+                }
+            }
+        })
+        .build();
+
+    // Returns once the window is closed
+    run_offscreen(vec![utils.offscreen_window(win_config)]);
+
+    let frames = frames.lock().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!((frames[0].width, frames[0].height), (100, 100));
+    assert_eq!(frames[0].pixel(25, 50), Some([255, 0, 0, 255]));
+    assert_eq!((frames[1].width, frames[1].height), (60, 40));
+    assert_eq!(frames[1].pixel(45, 20), Some([255, 255, 255, 255]));
+}
+
+const BLUE_TOP_PNG: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/snapshots/match_snapshot.png"
+);
+
+#[tokio::test]
+async fn use_image_loads_file() {
+    fn image_app(cx: Scope) -> Element {
+        let image = use_image(&cx, BLUE_TOP_PNG);
+
+        match image.get() {
+            ImageState::Loaded(bytes) => render!(image {
+                width: "100%",
+                height: "100%",
+                image_data: bytes,
+            }),
+            _ => render!(label { "Loading" }),
+        }
+    }
+
+    let mut utils = launch_test(image_app);
+
+    // The label is replaced with the image once it's loaded
+    while utils.root().child(0).unwrap().child(0).is_some() {
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            utils.wait_for_update((100.0, 100.0)),
+        )
+        .await
+        .expect("The image was never loaded");
+    }
+
+    let frame = utils.render_pixels((100.0, 100.0));
+    assert_eq!(frame.pixel(50, 25), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(50, 75), Some([255, 255, 255, 255]));
+}
+
+#[tokio::test]
+async fn report_undecodable_image_data() {
+    fn image_app(cx: Scope) -> Element {
+        render!(image {
+            width: "100%",
+            height: "100%",
+            image_data: b"freya!!".as_slice(),
+        })
+    }
+
+    let mut utils = launch_test(image_app);
+    utils.render_pixels((100.0, 100.0));
+
+    let errors = utils.attribute_errors();
+    let image_error = errors.iter().find(|e| e.attribute == "image_data").unwrap();
+    assert_eq!(
+        image_error.reason,
+        AttributeErrorReason::LoadFailed("unsupported image format".to_string())
+    );
+}
+
+#[tokio::test]
+async fn use_image_ignores_previous_paths() {
+    fn image_app(cx: Scope) -> Element {
+        let path = use_state(&cx, || BLUE_TOP_PNG.to_string());
+        let image = use_image(&cx, path.get());
+
+        // Change the path while the first one is still loading
+        use_effect(&cx, (), {
+            let path = path.clone();
+            move |_| async move {
+                path.set("missing.png".to_string());
+            }
+        });
+
+        let state = match image.get() {
+            ImageState::Loading => "Loading",
+            ImageState::Loaded(_) => "Loaded",
+            ImageState::Error(_) => "Error",
+        };
+
+        render!(label { "{state}" })
+    }
+
+    let mut utils = launch_test(image_app);
+    let label = utils.root().child(0).unwrap();
+
+    while label.child(0).unwrap().text() != Some("Error") {
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            utils.wait_for_update((100.0, 100.0)),
+        )
+        .await
+        .expect("The missing file never failed");
+    }
+
+    // The first file finishing its load later doesn't replace the error
+    let _ = tokio::time::timeout(
+        Duration::from_millis(500),
+        utils.wait_for_update((100.0, 100.0)),
+    )
+    .await;
+    assert_eq!(label.child(0).unwrap().text(), Some("Error"));
+}
+
+#[tokio::test]
+async fn render_linear_gradient() {
+    fn gradient_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "100%",
+            height: "100%",
+            background: "linear-gradient(to right, red, blue)",
+        })
+    }
+
+    let mut utils = launch_test(gradient_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    assert!(pixel_is_near(frame.pixel(1, 50), [255, 0, 0, 255], 8));
+    assert!(pixel_is_near(frame.pixel(50, 50), [128, 0, 128, 255], 8));
+    assert!(pixel_is_near(frame.pixel(98, 50), [0, 0, 255, 255], 8));
+    // Every column has a single color
+    assert_eq!(frame.pixel(25, 0), frame.pixel(25, 99));
+}
+
+#[tokio::test]
+async fn render_border_alignment() {
+    fn outer_border_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "50",
+            height: "50",
+            background: "blue",
+            border: "10 solid red",
+            border_align: "outer",
+        })
+    }
+
+    fn inner_border_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "50",
+            height: "50",
+            background: "blue",
+            border: "10 solid red",
+            border_align: "inner",
+        })
+    }
+
+    // Outer borders are drawn around the node
+    let frame = launch_test(outer_border_app).render_pixels((100.0, 100.0));
+    assert_eq!(frame.pixel(45, 25), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(55, 25), Some([255, 0, 0, 255]));
+    assert_eq!(frame.pixel(65, 25), Some([255, 255, 255, 255]));
+
+    // Inner borders are drawn over the edges of the node
+    let frame = launch_test(inner_border_app).render_pixels((100.0, 100.0));
+    assert_eq!(frame.pixel(35, 25), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(45, 25), Some([255, 0, 0, 255]));
+    assert_eq!(frame.pixel(55, 25), Some([255, 255, 255, 255]));
+}
+
+#[tokio::test]
+async fn render_corner_radius() {
+    fn rounded_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "100%",
+            height: "100%",
+            background: "blue",
+            radius: "50 0 0 0",
+        })
+    }
+
+    let mut utils = launch_test(rounded_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    // Only the top left corner is rounded
+    assert_eq!(frame.pixel(2, 2), Some([255, 255, 255, 255]));
+    assert_eq!(frame.pixel(97, 2), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(97, 97), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(2, 97), Some([0, 0, 255, 255]));
+}
+
+#[tokio::test]
+async fn render_inset_and_spread_shadows() {
+    fn shadows_app(cx: Scope) -> Element {
+        render!(rect {
+            width: "50",
+            height: "50",
+            background: "white",
+            shadow: "inset 0 0 255 0 10 red, 0 0 255 0 5 blue",
+        })
+    }
+
+    let mut utils = launch_test(shadows_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    // The inset shadow is spread inwards, but clipped to the node
+    assert_eq!(frame.pixel(45, 25), Some([255, 0, 0, 255]));
+    assert_eq!(frame.pixel(25, 25), Some([255, 255, 255, 255]));
+    // The outer shadow is spread outwards, under the node
+    assert_eq!(frame.pixel(52, 25), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(25, 52), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(58, 25), Some([255, 255, 255, 255]));
+}
+
+/// 30x30 PNG with red corners of 10x10 pixels, blue everywhere else
+fn red_corners_png() -> Vec<u8> {
+    let mut pixels = Vec::new();
+    for y in 0..30 {
+        for x in 0..30 {
+            let is_corner = !(10..20).contains(&x) && !(10..20).contains(&y);
+            if is_corner {
+                pixels.extend([255, 0, 0, 255]);
+            } else {
+                pixels.extend([0, 0, 255, 255]);
+            }
+        }
+    }
+    FramePixels {
+        width: 30,
+        height: 30,
+        pixels,
+    }
+    .encode_png()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn render_nine_slice_image() {
+    fn nine_slice_app(cx: Scope) -> Element {
+        let png: &Vec<u8> = cx.use_hook(red_corners_png);
+        render!(image {
+            width: "100",
+            height: "100",
+            image_data: png,
+            slice: "10",
+            sampling: "nearest",
+        })
+    }
+
+    let mut utils = launch_test(nine_slice_app);
+
+    let frame = utils.render_pixels((100.0, 100.0));
+
+    // The corners keep their size, the rest is stretched
+    assert_eq!(frame.pixel(5, 5), Some([255, 0, 0, 255]));
+    assert_eq!(frame.pixel(94, 94), Some([255, 0, 0, 255]));
+    assert_eq!(frame.pixel(15, 5), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(5, 15), Some([0, 0, 255, 255]));
+    assert_eq!(frame.pixel(50, 50), Some([0, 0, 255, 255]));
+}
+
+static DEJAVU_SANS_MONO: &[u8] = include_bytes!("./fonts/DejaVuSansMono.ttf");
+
+#[tokio::test]
+async fn render_text_decoration_and_shadow() {
+    fn text_app(cx: Scope) -> Element {
+        render!(
+            label {
+                font_family: "DejaVu Sans Mono",
+                font_size: "40",
+                color: "black",
+                decoration: "underline solid red",
+                "Freya"
+            }
+            label {
+                font_family: "DejaVu Sans Mono",
+                font_size: "40",
+                color: "white",
+                text_shadow: "4 4 0 blue",
+                "Freya"
+            }
+        )
+    }
+
+    let mut utils = launch_test(text_app);
+    // Bundled so the glyphs don't depend on the fonts of the system
+    utils.register_font(DEJAVU_SANS_MONO, "DejaVu Sans Mono");
+
+    let frame = utils.render_pixels((200.0, 120.0));
+
+    let rows_with = |color: [u8; 4]| {
+        (0..frame.height)
+            .filter(|y| (0..frame.width).any(|x| frame.pixel(x, *y) == Some(color)))
+            .collect::<Vec<i32>>()
+    };
+
+    // The underline is a red line under the black text of the first label
+    let red_rows = rows_with([255, 0, 0, 255]);
+    let black_rows = rows_with([0, 0, 0, 255]);
+    assert!(!red_rows.is_empty());
+    assert!(!black_rows.is_empty());
+    assert!(red_rows[0] > black_rows[0]);
+    assert!(red_rows.iter().all(|y| *y < 60));
+
+    // The white text of the second label is only visible through its shadow
+    let blue_rows = rows_with([0, 0, 255, 255]);
+    assert!(!blue_rows.is_empty());
+    assert!(blue_rows.iter().all(|y| *y >= 40));
+}